@@ -1,20 +1,39 @@
 use std::env;
 use std::process::exit;
+use std::time::Duration;
 
 use libdrsat::{SolverResult, driver};
 
-fn usage(name: &str) {
-	println!("usage: {} <PATH>", name);
+fn usage(name: &str) -> ! {
+	println!("usage: {} [--timeout SECONDS] [--conflicts N] <PATH> [PROOF]", name);
+	exit(1);
 }
 
 fn main() {
 	let args: Vec<_> = env::args().collect();
-	if args.len() != 2 {
-		usage(if !args.is_empty() { &args[0] } else { "comp" });
-		exit(1);
+	let name = if !args.is_empty() { &args[0] } else { "comp" };
+	let mut timeout = None;
+	let mut conflicts = None;
+	let mut positional = Vec::new();
+	let mut iter = args.iter().skip(1);
+	while let Some(arg) = iter.next() {
+		match arg.as_str() {
+			"--timeout" => match iter.next().and_then(|value| value.parse().ok()) {
+				Some(seconds) => timeout = Some(Duration::from_secs(seconds)),
+				None => usage(name),
+			},
+			"--conflicts" => match iter.next().and_then(|value| value.parse().ok()) {
+				Some(count) => conflicts = Some(count),
+				None => usage(name),
+			},
+			_ => positional.push(arg.as_str()),
+		}
+	}
+	if positional.len() != 1 && positional.len() != 2 {
+		usage(name);
 	}
 
-	match driver::comp::main(&args[1]) {
+	match driver::comp::main(positional[0], positional.get(1).copied(), timeout, conflicts) {
 		Ok(SolverResult::Unknown) => {
 			println!("s UNKNOWN");
 			exit(0);
@@ -10,6 +10,7 @@ struct Cli {
 
 #[derive(Debug, clap::Subcommand)]
 enum Commands {
+	CheckProof(driver::check_proof::Cli),
 	Completion(driver::completion::Cli),
 	Dimacs(driver::dimacs::Cli),
 	Npn(driver::npn::Cli),
@@ -20,6 +21,7 @@ enum Commands {
 fn run() -> Result<(), Error> {
 	let args = <Cli as clap::Parser>::parse();
 	match args.command {
+		Commands::CheckProof(args) => libdrsat::driver::check_proof::main(args),
 		Commands::Completion(args) => {
 			libdrsat::driver::completion::run_command(args, <Cli as clap::CommandFactory>::command())
 		}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use super::{DratStep, index};
+use crate::cnf::{ClauseLiteralVec, Literal};
+
+const NO_REASON: usize = usize::MAX;
+
+enum Event {
+	Add { cid: usize, line: usize },
+	Delete { cid: usize },
+}
+
+/// Checks a DRAT proof of the unsatisfiability of `clauses`.
+///
+/// The proof is first replayed to find the empty clause, and then checked backwards, so that only those lemmas are
+/// verified which are actually needed to derive it (the core). Unit propagation prefers core clauses, which keeps the
+/// core small. Lemmas that are not RUP are checked for being RAT on their first literal. Returns the number of lemmas
+/// that had to be verified.
+pub fn check_drat(clauses: &[ClauseLiteralVec], steps: &[(usize, DratStep)]) -> Result<usize, super::errors::Error> {
+	let variables = clauses
+		.iter()
+		.map(|clause| clause.as_slice())
+		.chain(steps.iter().map(|(_, step)| match step {
+			DratStep::Add(literals) | DratStep::Delete(literals) => literals.as_slice(),
+		}))
+		.flatten()
+		.map(|lit| lit.id().to_usize() + 1)
+		.max()
+		.unwrap_or(0);
+	let mut checker = Checker::new(variables);
+	let mut lookup: HashMap<Vec<Literal>, Vec<usize>> = HashMap::new();
+	for clause in clauses {
+		let cid = checker.add(clause, None);
+		lookup.entry(checker.clauses[cid].clone()).or_default().push(cid);
+	}
+
+	let mut events = Vec::new();
+	let mut empty = None;
+	for &(line, ref step) in steps {
+		match step {
+			DratStep::Add(literals) => {
+				let cid = checker.add(literals, literals.first().copied());
+				events.push(Event::Add { cid, line });
+				if literals.is_empty() {
+					empty = Some(cid);
+					break;
+				}
+				lookup.entry(checker.clauses[cid].clone()).or_default().push(cid);
+			}
+			DratStep::Delete(literals) => {
+				// deletions of unknown clauses are ignored, just like drat-trim does
+				if let Some(cid) = lookup.get_mut(&normalize(literals)).and_then(|cids| cids.pop()) {
+					checker.active[cid] = false;
+					events.push(Event::Delete { cid });
+				}
+			}
+		}
+	}
+	let empty = empty.ok_or(super::errors::Error::MissingEmptyClause)?;
+
+	checker.core[empty] = true;
+	let mut lemmas = 0;
+	for event in events.iter().rev() {
+		match *event {
+			Event::Add { cid, line } => {
+				checker.active[cid] = false;
+				if checker.core[cid] {
+					lemmas += 1;
+					if !checker.verify(cid) {
+						return Err(super::errors::Error::NotImplied { line });
+					}
+				}
+			}
+			Event::Delete { cid } => checker.active[cid] = true,
+		}
+	}
+	Ok(lemmas)
+}
+
+fn normalize(literals: &[Literal]) -> Vec<Literal> {
+	let mut literals = literals.to_vec();
+	literals.sort();
+	literals.dedup();
+	literals
+}
+
+struct Checker {
+	// the first two literals of each clause are watched
+	clauses: Vec<Vec<Literal>>,
+	pivots: Vec<Option<Literal>>,
+	active: Vec<bool>,
+	core: Vec<bool>,
+	units: Vec<usize>,
+	// indexed by the watched literal, whose clauses are visited when it becomes false
+	watches: Vec<Vec<usize>>,
+	truth: Vec<bool>,
+	reasons: Vec<usize>,
+	seen: Vec<bool>,
+	trail: Vec<Literal>,
+}
+
+impl Checker {
+	fn new(variables: usize) -> Checker {
+		Checker {
+			clauses: Vec::new(),
+			pivots: Vec::new(),
+			active: Vec::new(),
+			core: Vec::new(),
+			units: Vec::new(),
+			watches: vec![Vec::new(); 2 * variables],
+			truth: vec![false; 2 * variables],
+			reasons: vec![NO_REASON; variables],
+			seen: vec![false; variables],
+			trail: Vec::with_capacity(variables),
+		}
+	}
+
+	fn add(&mut self, literals: &[Literal], pivot: Option<Literal>) -> usize {
+		let cid = self.clauses.len();
+		let literals = normalize(literals);
+		match literals.len() {
+			0 => {}
+			1 => self.units.push(cid),
+			_ => {
+				self.watches[index(literals[0])].push(cid);
+				self.watches[index(literals[1])].push(cid);
+			}
+		}
+		self.clauses.push(literals);
+		self.pivots.push(pivot);
+		self.active.push(true);
+		self.core.push(false);
+		cid
+	}
+
+	fn value(&self, lit: Literal) -> Option<bool> {
+		if self.truth[index(lit)] {
+			Some(true)
+		} else if self.truth[index(lit.negate())] {
+			Some(false)
+		} else {
+			None
+		}
+	}
+
+	fn assign(&mut self, lit: Literal, reason: usize) {
+		self.truth[index(lit)] = true;
+		self.reasons[lit.id().to_usize()] = reason;
+		self.trail.push(lit);
+	}
+
+	fn verify(&mut self, cid: usize) -> bool {
+		let lemma = self.clauses[cid].clone();
+		if self.rup(&lemma) {
+			return true;
+		}
+		let Some(pivot) = self.pivots[cid] else {
+			return false;
+		};
+		// every resolvent on the pivot has to be RUP
+		let candidates: Vec<usize> = (0..self.clauses.len())
+			.filter(|&other| self.active[other] && self.clauses[other].contains(&pivot.negate()))
+			.collect();
+		for other in candidates {
+			let mut resolvent = lemma.clone();
+			resolvent.extend(self.clauses[other].iter().copied().filter(|&lit| lit != pivot.negate()));
+			if !self.rup(&resolvent) {
+				return false;
+			}
+			self.core[other] = true;
+		}
+		true
+	}
+
+	// checks whether the negation of `literals` leads to a conflict, marking the clauses involved as core
+	fn rup(&mut self, literals: &[Literal]) -> bool {
+		debug_assert!(self.trail.is_empty());
+		let mut conflict = None;
+		for &lit in literals {
+			match self.value(lit) {
+				Some(true) => {
+					// the clause contains both x and ¬x
+					self.backtrack();
+					return true;
+				}
+				Some(false) => {}
+				None => self.assign(lit.negate(), NO_REASON),
+			}
+		}
+		for i in 0..self.units.len() {
+			let unit = self.units[i];
+			if !self.active[unit] {
+				continue;
+			}
+			let lit = self.clauses[unit][0];
+			match self.value(lit) {
+				Some(true) => {}
+				Some(false) => {
+					conflict = Some(unit);
+					break;
+				}
+				None => self.assign(lit, unit),
+			}
+		}
+		if conflict.is_none() {
+			conflict = self.propagate();
+		}
+		if let Some(conflict) = conflict {
+			self.mark_core(conflict);
+		}
+		self.backtrack();
+		conflict.is_some()
+	}
+
+	// propagates with core clauses first and only falls back to the other clauses when they are exhausted
+	fn propagate(&mut self) -> Option<usize> {
+		let mut core_head = 0;
+		let mut head = 0;
+		loop {
+			let (lit, core) = if core_head < self.trail.len() {
+				core_head += 1;
+				(self.trail[core_head - 1], true)
+			} else if head < self.trail.len() {
+				head += 1;
+				(self.trail[head - 1], false)
+			} else {
+				return None;
+			};
+			if let Some(conflict) = self.propagate_literal(lit, core) {
+				return Some(conflict);
+			}
+		}
+	}
+
+	fn propagate_literal(&mut self, lit: Literal, core: bool) -> Option<usize> {
+		let falsified = lit.negate();
+		let mut watches = std::mem::take(&mut self.watches[index(falsified)]);
+		let mut conflict = None;
+		let mut i = 0;
+		'watches: while i < watches.len() {
+			let cid = watches[i];
+			if !self.active[cid] || self.core[cid] != core {
+				i += 1;
+				continue;
+			}
+			if self.clauses[cid][0] == falsified {
+				self.clauses[cid].swap(0, 1);
+			}
+			let other = self.clauses[cid][0];
+			if self.value(other) == Some(true) {
+				i += 1;
+				continue;
+			}
+			for k in 2..self.clauses[cid].len() {
+				let candidate = self.clauses[cid][k];
+				if self.value(candidate) != Some(false) {
+					self.clauses[cid].swap(1, k);
+					self.watches[index(candidate)].push(cid);
+					watches.swap_remove(i);
+					continue 'watches;
+				}
+			}
+			if self.value(other) == Some(false) {
+				conflict = Some(cid);
+				break;
+			}
+			self.assign(other, cid);
+			i += 1;
+		}
+		self.watches[index(falsified)] = watches;
+		conflict
+	}
+
+	fn mark_core(&mut self, conflict: usize) {
+		self.core[conflict] = true;
+		for lit in self.clauses[conflict].iter() {
+			self.seen[lit.id().to_usize()] = true;
+		}
+		for &lit in self.trail.iter().rev() {
+			let vid = lit.id().to_usize();
+			if !self.seen[vid] {
+				continue;
+			}
+			self.seen[vid] = false;
+			let reason = self.reasons[vid];
+			if reason != NO_REASON {
+				self.core[reason] = true;
+				for other in self.clauses[reason].iter().filter(|other| other.id() != lit.id()) {
+					self.seen[other.id().to_usize()] = true;
+				}
+			}
+		}
+	}
+
+	fn backtrack(&mut self) {
+		for lit in self.trail.drain(..) {
+			self.truth[index(lit)] = false;
+		}
+	}
+}
@@ -0,0 +1,17 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+	#[error("The lemma on line {line} is not implied by the preceding clauses")]
+	NotImplied { line: usize },
+
+	#[error("The proof does not derive the empty clause")]
+	MissingEmptyClause,
+
+	#[error("Unknown clause id {id} on line {line}")]
+	UnknownClause { id: u64, line: usize },
+
+	#[error("Clause id {id} on line {line} is already in use")]
+	DuplicateClause { id: u64, line: usize },
+
+	#[error("The RAT hints on line {line} are not supported")]
+	UnsupportedRat { line: usize },
+}
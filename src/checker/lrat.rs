@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use super::{LratStep, index};
+use crate::cnf::{ClauseLiteralVec, Literal};
+
+/// Checks an LRAT proof of the unsatisfiability of `clauses`, whose ids are their positions starting at 1.
+///
+/// As the hints already describe each unit propagation, every lemma is checked in a single forward pass. Returns the
+/// number of checked lemmas.
+pub fn check_lrat(clauses: &[ClauseLiteralVec], steps: &[(usize, LratStep)]) -> Result<usize, super::errors::Error> {
+	let mut db: HashMap<u64, Vec<Literal>> = clauses
+		.iter()
+		.enumerate()
+		.map(|(i, clause)| (i as u64 + 1, clause.to_vec()))
+		.collect();
+	let mut truth: Vec<bool> = Vec::new();
+	let mut trail: Vec<Literal> = Vec::new();
+	let mut lemmas = 0;
+	for &(line, ref step) in steps {
+		match step {
+			LratStep::Delete(ids) => {
+				for &id in ids {
+					if db.remove(&id).is_none() {
+						return Err(super::errors::Error::UnknownClause { id, line });
+					}
+				}
+			}
+			LratStep::Add { id, literals, hints } => {
+				if db.contains_key(id) {
+					return Err(super::errors::Error::DuplicateClause { id: *id, line });
+				}
+				lemmas += 1;
+				let implied = check_lemma(&db, literals, hints, line, &mut truth, &mut trail);
+				for lit in trail.drain(..) {
+					truth[index(lit)] = false;
+				}
+				if !implied? {
+					return Err(super::errors::Error::NotImplied { line });
+				}
+				if literals.is_empty() {
+					return Ok(lemmas);
+				}
+				db.insert(*id, literals.clone());
+			}
+		}
+	}
+	Err(super::errors::Error::MissingEmptyClause)
+}
+
+fn check_lemma(
+	db: &HashMap<u64, Vec<Literal>>,
+	literals: &[Literal],
+	hints: &[i64],
+	line: usize,
+	truth: &mut Vec<bool>,
+	trail: &mut Vec<Literal>,
+) -> Result<bool, super::errors::Error> {
+	let mut assign = |lit: Literal, truth: &mut Vec<bool>| {
+		if truth.len() <= index(lit) {
+			truth.resize(index(lit) + 2, false);
+		}
+		truth[index(lit)] = true;
+		trail.push(lit);
+	};
+	let is_true = |lit: Literal, truth: &[bool]| truth.get(index(lit)).copied().unwrap_or(false);
+
+	for &lit in literals {
+		if is_true(lit, truth) {
+			// the lemma contains both x and ¬x
+			return Ok(true);
+		}
+		if !is_true(lit.negate(), truth) {
+			assign(lit.negate(), truth);
+		}
+	}
+	for &hint in hints {
+		if hint < 0 {
+			return Err(super::errors::Error::UnsupportedRat { line });
+		}
+		let id = hint as u64;
+		let clause = db.get(&id).ok_or(super::errors::Error::UnknownClause { id, line })?;
+		let mut unit = None;
+		let mut open = 0;
+		for &lit in clause {
+			if is_true(lit, truth) {
+				// a superfluous hint does not harm
+				open = usize::MAX;
+				break;
+			}
+			if !is_true(lit.negate(), truth) && unit != Some(lit) {
+				unit = Some(lit);
+				open += 1;
+			}
+		}
+		match open {
+			0 => return Ok(true),
+			1 => assign(unit.unwrap(), truth),
+			usize::MAX => {}
+			_ => return Ok(false),
+		}
+	}
+	Ok(false)
+}
@@ -0,0 +1,39 @@
+//! Checking of DRAT and LRAT proofs for unsatisfiable CNF formulas
+
+mod drat;
+pub use self::drat::check_drat;
+
+pub mod errors;
+
+mod lrat;
+pub use self::lrat::check_lrat;
+
+#[cfg(test)]
+mod tests;
+
+use crate::cnf::Literal;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DratStep {
+	/// A lemma, which has to be RUP or RAT on its first literal
+	Add(Vec<Literal>),
+	/// Deletion of a clause with the given literals, in any order
+	Delete(Vec<Literal>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LratStep {
+	/// A lemma together with the ids of the clauses that make it RUP, in the order in which they become unit
+	Add {
+		id: u64,
+		literals: Vec<Literal>,
+		hints: Vec<i64>,
+	},
+	/// Deletion of the clauses with the given ids
+	Delete(Vec<u64>),
+}
+
+// literals are used as indices into per-literal tables
+fn index(lit: Literal) -> usize {
+	2 * lit.id().to_usize() + lit.negated() as usize
+}
@@ -0,0 +1,152 @@
+use super::*;
+use crate::SolverResult;
+use crate::cnf::{ClauseLiteralVec, ProblemBuilder, Proof, ProofFormat, SolverConfig, VariableId};
+use crate::util::SharedBuffer;
+
+fn lit(value: i64) -> Literal {
+	Literal::new(VariableId::from_usize(value.unsigned_abs() as usize - 1), value < 0)
+}
+
+// 4 pigeons do not fit into 3 holes
+fn pigeonhole() -> Vec<ClauseLiteralVec> {
+	let var = |pigeon: i64, hole: i64| 3 * pigeon + hole + 1;
+	let mut clauses = Vec::new();
+	for pigeon in 0..4 {
+		clauses.push((0..3).map(|hole| lit(var(pigeon, hole))).collect());
+	}
+	for hole in 0..3 {
+		for a in 0..4 {
+			for b in a + 1..4 {
+				clauses.push([lit(-var(a, hole)), lit(-var(b, hole))].into_iter().collect());
+			}
+		}
+	}
+	clauses
+}
+
+fn prove(format: ProofFormat) -> Vec<u8> {
+	let buffer = SharedBuffer::default();
+	let names: Vec<usize> = (1..=12).collect();
+	let mut problem = crate::cnf::Problem::with_proof(names, pigeonhole(), Proof::new(buffer.clone(), format));
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+	problem.finish_proof().unwrap();
+	buffer.take()
+}
+
+#[test]
+fn drat() {
+	for format in [ProofFormat::Drat, ProofFormat::BinaryDrat] {
+		let proof = prove(format);
+		let steps = crate::parser::proof::parse_drat(&mut proof.as_slice()).unwrap();
+		assert!(check_drat(&pigeonhole(), &steps).unwrap() > 0);
+	}
+}
+
+#[test]
+fn lrat() {
+	for format in [ProofFormat::Lrat, ProofFormat::BinaryLrat] {
+		let proof = prove(format);
+		let steps = crate::parser::proof::parse_lrat(&mut proof.as_slice()).unwrap();
+		assert!(check_lrat(&pigeonhole(), &steps).unwrap() > 0);
+	}
+}
+
+#[test]
+fn drat_rejected() {
+	let proof = b"c the first lemma is not implied\n1 2 0\n0\n";
+	let steps = crate::parser::proof::parse_drat(&mut proof.as_slice()).unwrap();
+	assert!(matches!(
+		check_drat(&pigeonhole(), &steps),
+		Err(errors::Error::NotImplied { line: 3 })
+	));
+}
+
+#[test]
+fn drat_incomplete() {
+	let proof = b"-1 -2 0\n";
+	let steps = crate::parser::proof::parse_drat(&mut proof.as_slice()).unwrap();
+	assert!(matches!(
+		check_drat(&pigeonhole(), &steps),
+		Err(errors::Error::MissingEmptyClause)
+	));
+}
+
+#[test]
+fn lrat_rejected() {
+	// the unit clause 4 needs both clause 1 and clause 2
+	let clauses: Vec<ClauseLiteralVec> = vec![
+		[lit(1), lit(2)].into_iter().collect(),
+		[lit(1), lit(-2)].into_iter().collect(),
+		[lit(-1)].into_iter().collect(),
+	];
+	let proof = b"4 1 0 1 0\n5 0 3 4 0\n";
+	let steps = crate::parser::proof::parse_lrat(&mut proof.as_slice()).unwrap();
+	assert!(matches!(
+		check_lrat(&clauses, &steps),
+		Err(errors::Error::NotImplied { line: 1 })
+	));
+	let proof = b"4 1 0 1 2 0\n4 d 1 2 0\n5 0 3 4 0\n";
+	let steps = crate::parser::proof::parse_lrat(&mut proof.as_slice()).unwrap();
+	assert_eq!(check_lrat(&clauses, &steps).unwrap(), 2);
+}
+
+// solves `clauses`, which have to be satisfiable, adds the unit clause `unit` and returns the DRAT proof of the second
+// call, which has to find the problem unsatisfiable
+fn prove_incrementally(clauses: &[ClauseLiteralVec], unit: Literal, config: SolverConfig) -> Vec<u8> {
+	let buffer = SharedBuffer::default();
+	let mut pb = ProblemBuilder::new();
+	for clause in clauses {
+		let mut builder = pb.new_clause();
+		for lit in clause.iter() {
+			builder.add_literal(lit.id().to_usize(), lit.negated());
+		}
+	}
+	pb.set_proof(Proof::new(buffer.clone(), ProofFormat::Drat));
+	pb.set_config(config);
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.add_clause([unit].into_iter().collect());
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+	problem.finish_proof().unwrap();
+	buffer.take()
+}
+
+#[test]
+fn drat_incremental() {
+	// x1 <-> x2 <-> ... <-> x10, where x1 or x10 has to be true, so that ¬x6 makes it unsatisfiable
+	let mut clauses: Vec<ClauseLiteralVec> = Vec::new();
+	for i in 1..10 {
+		clauses.push([lit(-i), lit(i + 1)].into_iter().collect());
+		clauses.push([lit(i), lit(-(i + 1))].into_iter().collect());
+	}
+	clauses.push([lit(1), lit(10)].into_iter().collect());
+	let mut extended = clauses.clone();
+	extended.push([lit(-6)].into_iter().collect());
+	// the clauses of eliminated or substituted variables are restored by the second call
+	for substitute in [false, true] {
+		let config = SolverConfig {
+			eliminate: true,
+			substitute,
+			probe: true,
+			..SolverConfig::default()
+		};
+		let proof = prove_incrementally(&clauses, lit(-6), config);
+		let steps = crate::parser::proof::parse_drat(&mut proof.as_slice()).unwrap();
+		check_drat(&extended, &steps).unwrap();
+	}
+
+	// x1 <-> x2 ∧ x3 next to x2 xor x3, whose clauses are blocked, so that x1 makes it unsatisfiable
+	let clauses: Vec<ClauseLiteralVec> = [&[-1, 2][..], &[-1, 3], &[1, -2, -3], &[2, 3], &[-2, -3]]
+		.into_iter()
+		.map(|clause| clause.iter().copied().map(lit).collect())
+		.collect();
+	let config = SolverConfig {
+		eliminate_blocked: true,
+		..SolverConfig::default()
+	};
+	let proof = prove_incrementally(&clauses, lit(1), config);
+	let steps = crate::parser::proof::parse_drat(&mut proof.as_slice()).unwrap();
+	let mut extended = clauses.clone();
+	extended.push([lit(1)].into_iter().collect());
+	check_drat(&extended, &steps).unwrap();
+}
@@ -0,0 +1,109 @@
+use super::clause::Reason;
+use super::{ClauseRef, Literal, VariableId};
+
+/// The current assignment of all variables, which is kept apart from the watch lists and stored as one array per
+/// field, so that propagation only touches densely packed data
+#[derive(Debug, Clone, Default)]
+pub struct Assignment {
+	// indexed by literal, so that the value of a literal takes a single lookup
+	values: Vec<Option<bool>>,
+	depths: Vec<VariableId>,
+	antes: Vec<Reason>,
+	// the current value of assigned variables, and the last value of unassigned ones
+	phases: Vec<bool>,
+}
+
+impl Assignment {
+	pub fn new(variables: usize) -> Assignment {
+		Assignment {
+			values: vec![None; 2 * variables],
+			depths: vec![VariableId::MAX; variables],
+			antes: vec![Reason::NONE; variables],
+			phases: vec![false; variables],
+		}
+	}
+
+	pub fn add_variable(&mut self) {
+		self.values.extend([None, None]);
+		self.depths.push(VariableId::MAX);
+		self.antes.push(Reason::NONE);
+		self.phases.push(false);
+	}
+
+	/// The number of variables
+	pub fn len(&self) -> usize {
+		self.depths.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.depths.is_empty()
+	}
+
+	/// Whether `literal` is true, false, or unassigned
+	#[inline]
+	pub fn literal_value(&self, literal: Literal) -> Option<bool> {
+		self.values[literal.index()]
+	}
+
+	#[inline]
+	pub fn value(&self, id: VariableId) -> Option<bool> {
+		self.literal_value(Literal::new(id, false))
+	}
+
+	#[inline]
+	pub fn has_value(&self, id: VariableId) -> bool {
+		self.value(id).is_some()
+	}
+
+	#[inline]
+	pub fn get_value(&self, id: VariableId) -> bool {
+		debug_assert!(self.has_value(id));
+		self.value(id) == Some(true)
+	}
+
+	/// Assigns the variable, where `ante` is the clause that implied the value, if any
+	#[inline]
+	pub fn set(&mut self, id: VariableId, value: bool, depth: VariableId, ante: Option<ClauseRef>) {
+		let positive = Literal::new(id, false).index();
+		self.values[positive] = Some(value);
+		self.values[positive + 1] = Some(!value);
+		self.depths[id.to_usize()] = depth;
+		self.antes[id.to_usize()] = Reason::new(ante);
+		self.phases[id.to_usize()] = value;
+	}
+
+	#[inline]
+	pub fn unset(&mut self, id: VariableId) {
+		let positive = Literal::new(id, false).index();
+		self.values[positive] = None;
+		self.values[positive + 1] = None;
+		self.depths[id.to_usize()] = VariableId::MAX;
+	}
+
+	#[inline]
+	pub fn get_depth(&self, id: VariableId) -> VariableId {
+		self.depths[id.to_usize()]
+	}
+
+	/// The clause that implied the current value, which is `None` for decisions and unit clauses
+	#[inline]
+	pub fn get_ante(&self, id: VariableId) -> Option<ClauseRef> {
+		debug_assert!(self.has_value(id));
+		self.antes[id.to_usize()].get(Literal::new(id, !self.get_value(id)))
+	}
+
+	/// Updates the reason of the assignment after its clause has been moved
+	pub fn set_ante(&mut self, id: VariableId, ante: Option<ClauseRef>) {
+		debug_assert!(self.has_value(id));
+		self.antes[id.to_usize()] = Reason::new(ante);
+	}
+
+	/// The current value if the variable is assigned, the last value it had otherwise
+	pub fn get_phase(&self, id: VariableId) -> bool {
+		self.phases[id.to_usize()]
+	}
+
+	pub fn set_phase(&mut self, id: VariableId, value: bool) {
+		self.phases[id.to_usize()] = value;
+	}
+}
@@ -2,14 +2,8 @@ use std::io;
 
 use smallvec::SmallVec;
 
-use super::{Literal, Variable, VariableId};
-
-#[derive(Debug)]
-pub struct Clause {
-	literals: ClauseLiteralVec,
-	watched: [VariableId; 2],
-	glue: VariableId,
-}
+use super::{Assignment, Literal, Variable, VariableId, Watcher};
+use crate::util::Stamps;
 
 #[cfg(feature = "small_variable_ids")]
 pub type ClauseLiteralVec = SmallVec<[Literal; 6]>;
@@ -17,75 +11,357 @@ pub type ClauseLiteralVec = SmallVec<[Literal; 6]>;
 #[cfg(not(feature = "small_variable_ids"))]
 pub type ClauseLiteralVec = SmallVec<[Literal; 4]>;
 
+/// The position of a clause in a `ClauseArena`. 32 bits keep watch lists and reasons small, while still allowing for
+/// billions of literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArenaRef(u32);
+
+impl ArenaRef {
+	#[inline]
+	fn index(self) -> usize {
+		self.0 as usize
+	}
+}
+
+/// Refers to a clause, either by its position in the clause arena or, as binary clauses are not kept in the arena, by
+/// value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClauseRef {
+	Long(ArenaRef),
+	Binary([Literal; 2]),
+}
+
+impl ClauseRef {
+	pub fn literals<'a>(&'a self, clauses: &'a ClauseArena) -> &'a [Literal] {
+		match self {
+			ClauseRef::Long(cref) => clauses.literals(*cref),
+			ClauseRef::Binary(literals) => literals,
+		}
+	}
+
+	/// The id of the clause in the proof. Binary clauses are looked up in the implications of their first literal, so
+	/// this is only meant for writing proofs.
+	pub fn id(&self, clauses: &ClauseArena, variables: &[Variable]) -> u64 {
+		match *self {
+			ClauseRef::Long(cref) => clauses.id(cref),
+			ClauseRef::Binary([lit, other]) => {
+				variables[lit.id().to_usize()]
+					.get_implications(lit.negated())
+					.iter()
+					.find(|implication| implication.literal == other)
+					.expect("binary clauses stay in the implications while they are referenced")
+					.id
+			}
+		}
+	}
+}
+
+// the tags of `Reason`, in its lowest bits
+const NO_REASON: u64 = 0;
+const LONG_REASON: u64 = 1;
+const BINARY_REASON: u64 = 2;
+const REASON_TAG_BITS: u32 = 2;
+
+/// The reason of an assignment, which is an `Option<ClauseRef>` packed into 64 bits to keep the assignment small. The
+/// first literal of a binary reason is the implied one, so only the other literal is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reason(u64);
+
+impl Reason {
+	pub const NONE: Reason = Reason(NO_REASON);
+
+	#[inline]
+	pub fn new(ante: Option<ClauseRef>) -> Reason {
+		match ante {
+			None => Reason::NONE,
+			Some(ClauseRef::Long(cref)) => Reason(((cref.0 as u64) << REASON_TAG_BITS) | LONG_REASON),
+			Some(ClauseRef::Binary([_, other])) => {
+				let raw = other.to_raw() as u64;
+				debug_assert!(raw << REASON_TAG_BITS >> REASON_TAG_BITS == raw);
+				Reason((raw << REASON_TAG_BITS) | BINARY_REASON)
+			}
+		}
+	}
+
+	/// Unpacks the reason of the assignment that made `implied` true
+	#[inline]
+	pub fn get(self, implied: Literal) -> Option<ClauseRef> {
+		let value = self.0 >> REASON_TAG_BITS;
+		match self.0 & ((1 << REASON_TAG_BITS) - 1) {
+			LONG_REASON => Some(ClauseRef::Long(ArenaRef(value as u32))),
+			BINARY_REASON => Some(ClauseRef::Binary([implied, Literal::from_raw(value as usize)])),
+			_ => None,
+		}
+	}
+}
+
+/// The outcome of visiting a clause after one of its watched literals has become false
 #[derive(Debug)]
 pub enum Apply {
-	Continue,
-	Unsat,
+	/// The clause is satisfied by the given literal and stays watched by the false literal
+	Satisfied(Literal),
+	/// The false literal was replaced by another watched literal
+	Moved,
 	Unit(Literal),
+	Unsat,
 }
 
-impl Clause {
-	pub fn new(literals: ClauseLiteralVec, glue: VariableId) -> Clause {
-		debug_assert!(literals.is_sorted());
+// Every clause is stored as a header followed by its literals. The header consists of 32 bit words, which are packed
+// into as few literal sized slots as possible, so that the literals of a clause can be handed out as a plain slice.
+const SIZE: usize = 0;
+// the glue in the lower bits and the flags in the upper ones
+const GLUE_FLAGS: usize = 1;
+const WATCHED: usize = 2;
+const ACTIVITY: usize = 4;
+// the lower and the upper half of the id
+const ID: usize = 5;
+const WORDS: usize = 7;
+const WORDS_PER_SLOT: usize = (Literal::BITS / u32::BITS) as usize;
+const HEADER: usize = WORDS.div_ceil(WORDS_PER_SLOT);
+
+// the position of the header word `word` within its slot
+#[inline]
+const fn shift(word: usize) -> u32 {
+	word as u32 * u32::BITS % Literal::BITS
+}
+
+const LEARNED: u32 = 1 << 31;
+const GARBAGE: u32 = 1 << 30;
+// set whenever the clause takes part in conflict analysis
+const USED: u32 = 1 << 29;
+// higher glues are stored as this value, which is far beyond any glue that makes a difference
+const MAX_GLUE: u32 = USED - 1;
+
+/// Stores all clauses with more than two literals in a single buffer
+#[derive(Debug, Default)]
+pub struct ClauseArena {
+	slots: Vec<Literal>,
+	live: usize,
+	// the number of slots occupied by deleted clauses
+	garbage: usize,
+}
+
+/// Maps the positions of the clauses before a `ClauseArena::compact` to their new positions
+#[derive(Debug)]
+pub struct Relocations(Vec<(ArenaRef, ArenaRef)>);
+
+impl Relocations {
+	/// The new position of the clause at `old`, which must not have been deleted
+	pub fn get(&self, old: ArenaRef) -> ArenaRef {
+		let index = self
+			.0
+			.binary_search_by_key(&old, |&(from, _)| from)
+			.expect("deleted clauses are not relocated");
+		self.0[index].1
+	}
+}
+
+impl ClauseArena {
+	pub fn new() -> ClauseArena {
+		Default::default()
+	}
+
+	/// The number of clauses that have not been deleted
+	pub fn len(&self) -> usize {
+		self.live
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.live == 0
+	}
+
+	/// Adds a clause whose literals at the positions `watched` are going to be watched
+	pub fn push(
+		&mut self,
+		id: u64,
+		literals: &[Literal],
+		glue: VariableId,
+		learned: bool,
+		watched: [usize; 2],
+	) -> ArenaRef {
+		debug_assert!(literals.len() > 2);
+		let cref = ArenaRef(u32::try_from(self.slots.len()).expect("the clause arena is limited to 2^32 slots"));
+		self.slots.resize(self.slots.len() + HEADER, Literal::from_raw(0));
+		// the arena is too small for clauses with more than 2^32 literals anyway
+		self.set(cref, SIZE, literals.len() as u32);
+		let glue = glue.to_usize().min(MAX_GLUE as usize) as u32;
+		self.set(cref, GLUE_FLAGS, if learned { glue | LEARNED } else { glue });
+		self.set_watched(cref, watched);
+		self.set_activity(cref, 0.0);
+		self.set(cref, ID, id as u32);
+		self.set(cref, ID + 1, (id >> u32::BITS) as u32);
+		self.slots.extend_from_slice(literals);
+		self.live += 1;
+		cref
+	}
+
+	// the header word `word` of the clause
+	#[inline]
+	fn get(&self, cref: ArenaRef, word: usize) -> u32 {
+		let raw = self.slots[cref.index() + word / WORDS_PER_SLOT].to_raw();
+		(raw >> shift(word)) as u32
+	}
+
+	#[inline]
+	fn set(&mut self, cref: ArenaRef, word: usize, value: u32) {
+		let slot = &mut self.slots[cref.index() + word / WORDS_PER_SLOT];
+		let shift = shift(word);
+		let raw = slot.to_raw() & !((u32::MAX as usize) << shift) | (value as usize) << shift;
+		*slot = Literal::from_raw(raw);
+	}
+
+	#[inline]
+	fn size(&self, cref: ArenaRef) -> usize {
+		self.get(cref, SIZE) as usize
+	}
+
+	#[inline]
+	fn watched(&self, cref: ArenaRef) -> [usize; 2] {
+		[self.get(cref, WATCHED) as usize, self.get(cref, WATCHED + 1) as usize]
+	}
+
+	#[inline]
+	fn set_watched(&mut self, cref: ArenaRef, watched: [usize; 2]) {
+		self.set(cref, WATCHED, watched[0] as u32);
+		self.set(cref, WATCHED + 1, watched[1] as u32);
+	}
+
+	#[inline]
+	fn flags(&self, cref: ArenaRef) -> u32 {
+		self.get(cref, GLUE_FLAGS) & !MAX_GLUE
+	}
+
+	#[inline]
+	fn set_flags(&mut self, cref: ArenaRef, flags: u32) {
+		let glue = self.get(cref, GLUE_FLAGS) & MAX_GLUE;
+		self.set(cref, GLUE_FLAGS, glue | flags);
+	}
+
+	#[inline]
+	pub fn literals(&self, cref: ArenaRef) -> &[Literal] {
+		let start = cref.index() + HEADER;
+		&self.slots[start..start + self.size(cref)]
+	}
+
+	/// The id of the clause in the proof, which does not change when the clause is moved
+	pub fn id(&self, cref: ArenaRef) -> u64 {
+		self.get(cref, ID) as u64 | (self.get(cref, ID + 1) as u64) << u32::BITS
+	}
+
+	/// The glue of the clause, where glues above 2^29 are cut off
+	pub fn get_glue(&self, cref: ArenaRef) -> VariableId {
+		VariableId::from_usize((self.get(cref, GLUE_FLAGS) & MAX_GLUE) as usize)
+	}
+
+	pub fn is_learned(&self, cref: ArenaRef) -> bool {
+		self.flags(cref) & LEARNED != 0
+	}
+
+	pub fn get_activity(&self, cref: ArenaRef) -> f32 {
+		f32::from_bits(self.get(cref, ACTIVITY))
+	}
+
+	pub fn set_activity(&mut self, cref: ArenaRef, activity: f32) {
+		self.set(cref, ACTIVITY, activity.to_bits());
+	}
 
-		Clause {
-			literals,
-			watched: [VariableId::from_usize(0), VariableId::from_usize(1)],
-			glue,
+	/// Multiplies the activities of all clauses by `factor`
+	pub fn scale_activities(&mut self, factor: f32) {
+		let mut pos = 0;
+		while pos < self.slots.len() {
+			let cref = ArenaRef(pos as u32);
+			pos += HEADER + self.size(cref);
+			let activity = self.get_activity(cref);
+			self.set_activity(cref, activity * factor);
 		}
 	}
 
-	pub fn from_learned(
-		mut literals: ClauseLiteralVec,
-		variables: &Vec<Variable>,
-		max_depth: VariableId,
-	) -> (VariableId, Literal, Clause) {
-		literals.sort();
-		let mut marks = vec![false; max_depth.to_usize() + 1];
-		let mut glue = VariableId::from_usize(0);
-		let mut da = VariableId::from_usize(0);
-		let mut pa = VariableId::from_usize(0);
-		let mut db = VariableId::from_usize(0);
-		let mut pb = VariableId::from_usize(0);
-		debug_assert!(literals.iter().all(|lit| variables[lit.id().to_usize()].has_value()));
-		for (i, depth) in literals
-			.iter()
-			.map(|lit| variables[lit.id().to_usize()].get_depth())
-			.enumerate()
-			.map(|(i, depth)| (VariableId::from_usize(i), depth))
-		{
-			if !marks[depth.to_usize()] {
-				glue = VariableId::from_usize(glue.to_usize() + 1);
-				marks[depth.to_usize()] = true;
-			}
-			if depth > da {
-				db = da;
-				pb = pa;
-				da = depth;
-				pa = i;
-			} else if depth > db {
-				db = depth;
-				pb = i;
-			}
+	pub fn mark_used(&mut self, cref: ArenaRef) {
+		let flags = self.flags(cref);
+		self.set_flags(cref, flags | USED);
+	}
+
+	/// Whether the clause was used since the last call, clearing the flag
+	pub fn take_used(&mut self, cref: ArenaRef) -> bool {
+		let flags = self.flags(cref);
+		self.set_flags(cref, flags & !USED);
+		flags & USED != 0
+	}
+
+	pub fn is_deleted(&self, cref: ArenaRef) -> bool {
+		self.flags(cref) & GARBAGE != 0
+	}
+
+	/// Whether the clause is the reason of a current assignment, which is then the first of its watched literals
+	pub fn is_reason(&self, cref: ArenaRef, assignment: &Assignment) -> bool {
+		let id = self.literals(cref)[self.watched(cref)[0]].id();
+		assignment.has_value(id) && assignment.get_ante(id) == Some(ClauseRef::Long(cref))
+	}
+
+	/// Marks the clause as deleted, after which it is skipped by `iter` and dropped by the next `compact`. The watches
+	/// of the clause are not removed, so they have to be dropped once they are encountered.
+	pub fn delete(&mut self, cref: ArenaRef) {
+		debug_assert!(!self.is_deleted(cref));
+		let flags = self.flags(cref);
+		self.set_flags(cref, flags | GARBAGE);
+		self.live -= 1;
+		self.garbage += HEADER + self.size(cref);
+	}
+
+	/// The fraction of the arena that is occupied by deleted clauses
+	pub fn fragmentation(&self) -> f64 {
+		if self.slots.is_empty() {
+			0.0
+		} else {
+			self.garbage as f64 / self.slots.len() as f64
 		}
-		let lit = literals[pa.to_usize()];
-		(
-			db,
-			lit,
-			Clause {
-				literals,
-				watched: [pa, pb],
-				glue,
-			},
-		)
 	}
 
-	pub fn iter(&self) -> ::std::slice::Iter<'_, Literal> {
-		self.literals.iter()
+	/// The clauses that have not been deleted, in the order in which they were added
+	pub fn iter(&self) -> impl Iterator<Item = ArenaRef> + '_ {
+		let mut pos = 0;
+		std::iter::from_fn(move || {
+			while pos < self.slots.len() {
+				let cref = ArenaRef(pos as u32);
+				pos += HEADER + self.size(cref);
+				if !self.is_deleted(cref) {
+					return Some(cref);
+				}
+			}
+			None
+		})
 	}
 
-	pub fn print<T: ::std::fmt::Display>(&self, f: &mut impl io::Write, variable_names: &Vec<T>) -> io::Result<()> {
-		for (i, literal) in self.literals.iter().enumerate() {
+	/// Removes the deleted clauses by moving the remaining ones to the front of the buffer. All references to the
+	/// remaining clauses have to be updated by means of the returned relocations.
+	pub fn compact(&mut self) -> Relocations {
+		let mut relocations = Vec::with_capacity(self.live);
+		let mut read = 0;
+		let mut write = 0;
+		while read < self.slots.len() {
+			let cref = ArenaRef(read as u32);
+			let len = HEADER + self.size(cref);
+			if !self.is_deleted(cref) {
+				if read != write {
+					self.slots.copy_within(read..read + len, write);
+				}
+				relocations.push((cref, ArenaRef(write as u32)));
+				write += len;
+			}
+			read += len;
+		}
+		self.slots.truncate(write);
+		self.garbage = 0;
+		Relocations(relocations)
+	}
+
+	pub fn print<T: ::std::fmt::Display>(
+		&self,
+		cref: ArenaRef,
+		f: &mut impl io::Write,
+		variable_names: &[T],
+	) -> io::Result<()> {
+		for (i, literal) in self.literals(cref).iter().enumerate() {
 			if i != 0 {
 				write!(f, " ")?;
 			}
@@ -94,53 +370,40 @@ impl Clause {
 		Ok(())
 	}
 
-	pub fn update_glue(&mut self, variables: &Vec<Variable>, max_depth: VariableId) {
-		if self.glue.to_usize() <= 2 {
+	/// Lowers the glue of the clause if its literals are currently assigned at fewer distinct depths. `depths` only
+	/// serves as scratch space.
+	pub fn update_glue(&mut self, cref: ArenaRef, assignment: &Assignment, depths: &mut Stamps) {
+		let old = self.get_glue(cref).to_usize();
+		if old <= 2 {
 			return;
 		}
-		let mut marks = vec![false; max_depth.to_usize() + 1];
-		let mut glue = VariableId::from_usize(0);
-		for depth in self
-			.literals
-			.iter()
-			.map(|lit| variables[lit.id().to_usize()].get_depth())
-		{
-			if !marks[depth.to_usize()] {
-				glue = VariableId::from_usize(glue.to_usize() + 1);
-				marks[depth.to_usize()] = true;
-				if glue >= self.glue {
+		depths.clear();
+		let mut glue = 0;
+		for depth in self.literals(cref).iter().map(|lit| assignment.get_depth(lit.id())) {
+			if depths.insert(depth.to_usize()) {
+				glue += 1;
+				if glue >= old {
 					return;
 				}
 			}
 		}
-		debug_assert!(glue < self.glue);
-		self.glue = glue;
-	}
-
-	pub fn get_glue(&self) -> VariableId {
-		self.glue
-	}
-
-	pub fn len(&self) -> usize {
-		self.literals.len()
-	}
-
-	pub fn is_empty(&self) -> bool {
-		self.literals.is_empty()
+		debug_assert!(glue < old);
+		let flags = self.flags(cref);
+		self.set(cref, GLUE_FLAGS, glue as u32 | flags);
 	}
 
 	/// The idea of this function is to distribute the (initial) watch list effort
 	/// fairly over all variables
-	pub fn initialize_watched(&mut self, cid: usize, variables: &mut Vec<Variable>) {
-		debug_assert!(self.literals.len() >= 2);
-		debug_assert!(self.literals[0] < self.literals[1]); // literals must already be sorted by the precomputation step!
+	pub fn initialize_watched(&mut self, cref: ArenaRef, assignment: &Assignment, variables: &mut [Variable]) {
+		let literals = self.literals(cref);
+		debug_assert!(literals.len() >= 2);
+		debug_assert!(literals[0] < literals[1]); // literals must already be sorted by the precomputation step!
 		let mut a = 0;
 		let mut sa = usize::MAX;
 		let mut b = 0;
 		let mut sb = usize::MAX;
-		for i in 0..self.literals.len() {
-			let lit = self.literals[i];
-			let len = variables[lit.id().to_usize()].get_clauses(lit.negated()).len();
+		for (i, lit) in literals.iter().enumerate() {
+			let len = variables[lit.id().to_usize()].get_watchers(lit.negated()).len();
 			if len < sa {
 				b = a;
 				sb = sa;
@@ -151,142 +414,180 @@ impl Clause {
 				sb = len;
 			}
 		}
-		self.watched[0] = VariableId::from_usize(a);
-		self.watched[1] = VariableId::from_usize(b);
 		debug_assert!(a != b);
-		self.notify_watched(cid, variables);
+		self.set_watched(cref, [a, b]);
+		self.notify_watched(cref, assignment, variables);
 	}
 
-	pub fn notify_watched(&self, cid: usize, variables: &mut Vec<Variable>) {
-		let lit0 = self.literals[self.watched[0].to_usize()];
-		if !variables[lit0.id().to_usize()].has_value() || variables[lit0.id().to_usize()].get_depth().to_usize() != 0 {
-			variables[lit0.id().to_usize()].watch(cid, lit0.negated());
-			let lit1 = self.literals[self.watched[1].to_usize()];
-			variables[lit1.id().to_usize()].watch(cid, lit1.negated());
+	pub fn notify_watched(&self, cref: ArenaRef, assignment: &Assignment, variables: &mut [Variable]) {
+		let literals = self.literals(cref);
+		let [a, b] = self.watched(cref);
+		let lit0 = literals[a];
+		if !assignment.has_value(lit0.id()) || assignment.get_depth(lit0.id()).to_usize() != 0 {
+			let lit1 = literals[b];
+			variables[lit0.id().to_usize()].watch(
+				Watcher {
+					cid: cref,
+					blocker: lit1,
+				},
+				lit0.negated(),
+			);
+			variables[lit1.id().to_usize()].watch(
+				Watcher {
+					cid: cref,
+					blocker: lit0,
+				},
+				lit1.negated(),
+			);
 		}
 	}
 
-	pub fn is_watched(&self, id: VariableId) -> bool {
-		self.literals[self.watched[0].to_usize()].id() == id || self.literals[self.watched[1].to_usize()].id() == id
-	}
-
-	pub fn apply(&mut self, cid: usize, variables: &mut Vec<Variable>) -> Apply {
-		let mut lit0 = self.literals[self.watched[0].to_usize()];
-		if let Some(val) = variables[lit0.id().to_usize()].value() {
-			if lit0.negated() != val {
-				return Apply::Continue;
-			}
+	/// Visits the clause after its watched literal `falsified` has become false. The caller is responsible for removing
+	/// the watch of `falsified` if the result is `Moved`.
+	pub fn apply(
+		&mut self,
+		cref: ArenaRef,
+		falsified: Literal,
+		assignment: &Assignment,
+		variables: &mut [Variable],
+	) -> Apply {
+		let mut watched = self.watched(cref);
+		let start = cref.index() + HEADER;
+		let len = self.size(cref);
+		if self.slots[start + watched[0]] == falsified {
+			watched.swap(0, 1);
+			self.set_watched(cref, watched);
 		}
-		let lit1 = self.literals[self.watched[1].to_usize()];
-		if let Some(val) = variables[lit1.id().to_usize()].value() {
-			if lit1.negated() != val {
-				return Apply::Continue;
-			}
+		debug_assert_eq!(self.slots[start + watched[1]], falsified);
+		let other = self.slots[start + watched[0]];
+		let value = assignment.literal_value(other);
+		if value == Some(true) {
+			return Apply::Satisfied(other);
 		}
 
-		let start = self.watched[0].to_usize();
-		let mut i = start;
-		loop {
-			if i + 1 == self.literals.len() {
-				i = 0;
-			} else {
-				i += 1;
+		for i in 0..len {
+			if i == watched[0] || i == watched[1] {
+				continue;
 			}
-			if i == self.watched[1].to_usize() {
-				if i + 1 == self.literals.len() {
-					i = 0;
-				} else {
-					i += 1;
-				}
-			}
-			if i == start {
-				break;
-			}
-			debug_assert!(i != self.watched[0].to_usize());
-			debug_assert!(i != self.watched[1].to_usize());
-			debug_assert!(i != start);
-			let lit = self.literals[i];
-			match variables[lit.id().to_usize()].value() {
+			let lit = self.slots[start + i];
+			match assignment.literal_value(lit) {
 				None => {
-					if variables[lit0.id().to_usize()].has_value() {
-						variables[lit0.id().to_usize()].unwatch(cid, lit0.negated());
-						variables[lit.id().to_usize()].watch(cid, lit.negated());
-						self.watched[0] = VariableId::from_usize(i);
-						if !variables[lit1.id().to_usize()].has_value() {
-							return Apply::Continue;
-						}
-						lit0 = lit
-					} else {
-						debug_assert!(variables[lit1.id().to_usize()].has_value());
-						variables[lit1.id().to_usize()].unwatch(cid, lit1.negated());
-						variables[lit.id().to_usize()].watch(cid, lit.negated());
-						self.watched[1] = VariableId::from_usize(i);
-						return Apply::Continue;
-					}
-				}
-				Some(val) => {
-					if lit.negated() != val {
-						self.percolate_sat(cid, variables, start, i, lit);
-						return Apply::Continue;
-					}
+					self.set(cref, WATCHED + 1, i as u32);
+					variables[lit.id().to_usize()].watch(
+						Watcher {
+							cid: cref,
+							blocker: other,
+						},
+						lit.negated(),
+					);
+					return Apply::Moved;
 				}
+				// all literals assigned so far are at most as deep as `falsified`, so the clause stays satisfied for
+				// as long as the watch is needed
+				Some(true) => return Apply::Satisfied(lit),
+				Some(false) => {}
 			}
 		}
-		if variables[lit0.id().to_usize()].has_value() {
-			if variables[lit1.id().to_usize()].has_value() {
-				Apply::Unsat
-			} else {
-				Apply::Unit(lit1)
-			}
-		} else if variables[lit1.id().to_usize()].has_value() {
-			Apply::Unit(lit0)
+		if value.is_none() {
+			Apply::Unit(other)
 		} else {
-			Apply::Continue
+			Apply::Unsat
 		}
 	}
+}
 
-	fn percolate_sat(&mut self, cid: usize, variables: &mut Vec<Variable>, start: usize, mut pos: usize, lit: Literal) {
-		let mut mind = variables[lit.id().to_usize()].get_depth();
-		let mut i = pos;
-		loop {
-			if i + 1 == self.literals.len() {
-				i = 0;
-			} else {
-				i += 1;
-			}
-			if i == self.watched[1].to_usize() {
-				if i + 1 == self.literals.len() {
-					i = 0;
-				} else {
-					i += 1;
-				}
-			}
-			if i == start {
-				break;
-			}
-			let d = variables[self.literals[i].id().to_usize()].get_depth();
-			if d < mind
-				&& (d.to_usize() != 0 || variables[self.literals[i].id().to_usize()].get_value() != self.literals[i].negated())
-			{
-				mind = d;
-				pos = i;
-			}
+/// Sorts the literals of a learned clause and returns its glue together with the positions of the two literals that
+/// were assigned last, which are the ones to be watched. The first of them is the asserting literal, while the depth
+/// of the second one is the one to backtrack to. `depths` only serves as scratch space.
+pub fn prepare_learned(
+	literals: &mut [Literal],
+	assignment: &Assignment,
+	depths: &mut Stamps,
+) -> (VariableId, [usize; 2]) {
+	literals.sort();
+	depths.clear();
+	let mut glue = 0;
+	let mut da = VariableId::from_usize(0);
+	let mut pa = 0;
+	let mut db = VariableId::from_usize(0);
+	let mut pb = 0;
+	debug_assert!(literals.iter().all(|lit| assignment.has_value(lit.id())));
+	for (i, depth) in literals.iter().map(|lit| assignment.get_depth(lit.id())).enumerate() {
+		if depths.insert(depth.to_usize()) {
+			glue += 1;
 		}
-		if variables[self.literals[pos].id().to_usize()].get_depth().to_usize() == 0 {
-			variables[self.literals[self.watched[0].to_usize()].id().to_usize()]
-				.unwatch(cid, self.literals[self.watched[0].to_usize()].negated());
-			variables[self.literals[self.watched[1].to_usize()].id().to_usize()]
-				.unwatch(cid, self.literals[self.watched[1].to_usize()].negated());
-		//self.glue = usize::MAX; // why does this actually *hurt*?!
-		} else if pos != self.watched[0].to_usize() {
-			if pos != self.watched[1].to_usize() {
-				variables[self.literals[self.watched[0].to_usize()].id().to_usize()]
-					.unwatch(cid, self.literals[self.watched[0].to_usize()].negated());
-				variables[self.literals[pos].id().to_usize()].watch(cid, self.literals[pos].negated());
-				self.watched[0] = VariableId::from_usize(pos);
-			} else {
-				self.watched.swap(0, 1);
-			}
+		if depth > da {
+			db = da;
+			pb = pa;
+			da = depth;
+			pa = i;
+		} else if depth > db {
+			db = depth;
+			pb = i;
+		}
+	}
+	(VariableId::from_usize(glue), [pa, pb])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn literals(ids: &[usize]) -> Vec<Literal> {
+		ids
+			.iter()
+			.map(|&id| Literal::new(VariableId::from_usize(id), id % 2 == 0))
+			.collect()
+	}
+
+	#[test]
+	fn arena_compaction() {
+		let mut arena = ClauseArena::new();
+		let glue = VariableId::from_usize(3);
+		let a = arena.push(1, &literals(&[0, 1, 2]), glue, false, [0, 1]);
+		let b = arena.push(u64::MAX - 1, &literals(&[3, 4, 5, 6]), glue, true, [2, 3]);
+		let c = arena.push(3, &literals(&[7, 8, 9]), glue, true, [1, 2]);
+		assert!(HEADER * size_of::<Literal>() <= 32);
+		assert_eq!(arena.len(), 3);
+		assert_eq!(arena.id(b), u64::MAX - 1);
+		assert!(!arena.is_learned(a));
+		assert!(arena.is_learned(b));
+
+		arena.delete(b);
+		assert!(arena.is_deleted(b));
+		assert!(arena.fragmentation() > 0.0);
+		assert_eq!(arena.iter().collect::<Vec<_>>(), vec![a, c]);
+		let relocations = arena.compact();
+		assert_eq!(arena.fragmentation(), 0.0);
+		assert_eq!(relocations.get(a), a);
+		let c = relocations.get(c);
+		assert_eq!(arena.iter().collect::<Vec<_>>(), vec![a, c]);
+		assert_eq!(arena.len(), 2);
+		assert_eq!(arena.literals(c), &literals(&[7, 8, 9])[..]);
+		assert_eq!(arena.id(c), 3);
+		assert_eq!(arena.watched(c), [1, 2]);
+		assert_eq!(arena.get_glue(c), glue);
+
+		arena.set_activity(c, 1.5);
+		arena.mark_used(c);
+		assert_eq!(arena.get_activity(c), 1.5);
+		assert!(arena.take_used(c));
+		assert!(!arena.take_used(c));
+		assert!(arena.is_learned(c));
+		assert_eq!(arena.get_glue(c), glue);
+		assert_eq!(arena.watched(c), [1, 2]);
+	}
+
+	#[test]
+	fn reasons() {
+		let lits = literals(&[7, 12]);
+		assert_eq!(size_of::<Reason>(), 8);
+		for ante in [
+			None,
+			Some(ClauseRef::Long(ArenaRef(u32::MAX))),
+			Some(ClauseRef::Binary([lits[0], lits[1]])),
+		] {
+			assert_eq!(Reason::new(ante).get(lits[0]), ante);
 		}
 	}
 }
@@ -0,0 +1,97 @@
+use crate::cnf::heuristic::BranchingPolicy;
+
+/// When the search is restarted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RestartPolicy {
+	/// Restart after the number of conflicts given by the Luby sequence times the restart interval
+	Luby,
+	/// Restart after a number of conflicts that starts at the restart interval and grows by the restart factor
+	Geometric,
+	/// Restart when the glue of recently learned clauses is high compared to the long term average
+	Glucose,
+}
+
+/// Tuning parameters of the search, which default to values that work well on a broad range of problems.
+///
+/// The preprocessing and inprocessing passes that remove, replace or add clauses (`eliminate`, `substitute`, `probe`,
+/// `eliminate_blocked` and `eliminate_tautologies`) are all opt-in, so that the clauses of a problem stay as they were
+/// given unless the caller asks otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverConfig {
+	pub branching: BranchingPolicy,
+	/// The initial step size of the ERWA updates of the CHB and LRB branching heuristics
+	pub alpha: f64,
+	/// The amount by which the step size is reduced after each conflict
+	pub alpha_decay: f64,
+	/// The step size is not reduced below this value
+	pub alpha_min: f64,
+	/// The reward of variables involved in propagations that did not result in a conflict, relative to conflicts, for
+	/// the CHB branching heuristic
+	pub propagation_reward: f64,
+	/// The factor by which the activities of the EVSIDS branching heuristic decay after each conflict
+	pub vsids_decay: f64,
+	/// The number of conflicts before learned clauses are deleted for the first time
+	pub reduce_interval: u32,
+	/// The amount by which the number of conflicts between deletions of learned clauses grows each time
+	pub reduce_increment: u32,
+	/// The fraction of the local tier of learned clauses with the lowest activities that is deleted each time
+	pub reduce_fraction: f64,
+	/// Learned clauses with at most this glue form the core tier, which is never deleted
+	pub core_glue: u32,
+	/// Learned clauses with at most this glue form tier 2, which is kept as long as its clauses keep being used in
+	/// conflict analysis, while all others go to the local tier
+	pub tier2_glue: u32,
+	/// The factor by which the activities of learned clauses decay after each conflict
+	pub clause_decay: f64,
+	pub restart_policy: RestartPolicy,
+	/// The unit of the Luby sequence, the initial interval of geometric restarts, or the minimum number of conflicts
+	/// between glucose-style restarts
+	pub restart_interval: u32,
+	/// The growth of the interval of geometric restarts
+	pub restart_factor: f64,
+	/// How much higher the short term average glue has to be than the long term average glue for a glucose-style
+	/// restart
+	pub restart_margin: f64,
+	/// Whether variables are eliminated by resolution during preprocessing, unless they are frozen
+	pub eliminate: bool,
+	/// Whether equivalent literals are substituted by a representative during preprocessing, followed by a transitive
+	/// reduction of the binary clauses
+	pub substitute: bool,
+	/// Whether failed literal probing with hyper-binary resolution runs during preprocessing and after reductions of
+	/// the learned clauses
+	pub probe: bool,
+	/// Whether clauses that are blocked on a literal are eliminated during preprocessing, which needs their witnesses
+	/// to be flipped in models
+	pub eliminate_blocked: bool,
+	/// Whether clauses that are hidden or asymmetric tautologies, and thus implied by the other clauses, are eliminated
+	/// during preprocessing
+	pub eliminate_tautologies: bool,
+}
+
+impl Default for SolverConfig {
+	fn default() -> Self {
+		SolverConfig {
+			branching: BranchingPolicy::Chb,
+			alpha: 0.4,
+			alpha_decay: 1e-6,
+			alpha_min: 0.06,
+			propagation_reward: 0.9,
+			vsids_decay: 0.95,
+			reduce_interval: 2047,
+			reduce_increment: 512,
+			reduce_fraction: 0.5,
+			core_glue: 2,
+			tier2_glue: 6,
+			clause_decay: 0.999,
+			restart_policy: RestartPolicy::Glucose,
+			restart_interval: 50,
+			restart_factor: 1.5,
+			restart_margin: 1.25,
+			eliminate: false,
+			substitute: false,
+			probe: false,
+			eliminate_blocked: false,
+			eliminate_tautologies: false,
+		}
+	}
+}
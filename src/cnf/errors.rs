@@ -0,0 +1,12 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+	#[error("There is no model, as the problem has not just been found to be satisfiable")]
+	NoModel,
+
+	#[error("The original clauses have not been kept, so the model can not be verified")]
+	NoOriginalClauses,
+
+	/// The clauses are numbered from 1 in the order in which they were given
+	#[error("The model falsifies clause {number} of the input")]
+	FalsifiedClause { number: usize },
+}
@@ -0,0 +1,118 @@
+use crate::cnf::{Assignment, SolverConfig, VariableId};
+use crate::util::Heap;
+
+/// Conflict History-Based branching, which keeps an exponential recency weighted average (ERWA) of the rewards of
+/// each variable. Variables are rewarded whenever they are assigned, and more so the more recently they occurred in
+/// a conflicting clause.
+#[derive(Debug)]
+pub struct Chb {
+	alpha: f64,
+	alpha_decay: f64,
+	alpha_min: f64,
+	propagation_reward: f64,
+	q: Vec<f64>,
+	last_conflict: Vec<u64>,
+	conflicts: u64,
+	// the variables assigned since the last update
+	plays: Vec<VariableId>,
+	heap: Heap,
+}
+
+impl Chb {
+	pub fn new(config: &SolverConfig) -> Chb {
+		Chb {
+			alpha: config.alpha,
+			alpha_decay: config.alpha_decay,
+			alpha_min: config.alpha_min,
+			propagation_reward: config.propagation_reward,
+			q: Vec::new(),
+			last_conflict: Vec::new(),
+			conflicts: 0,
+			plays: Vec::new(),
+			heap: Heap::new(),
+		}
+	}
+}
+
+impl super::BranchingHeuristic for Chb {
+	fn initialize(&mut self, scores: &[f64]) {
+		for &score in scores {
+			self.q.push(score);
+			self.last_conflict.push(0);
+			self.heap.push(self.q.len() - 1, |i| self.q[i]);
+		}
+	}
+
+	fn add_variable(&mut self) {
+		self.initialize(&[0.0]);
+	}
+
+	fn assigned(&mut self, id: VariableId) {
+		self.plays.push(id);
+	}
+
+	fn unassigned(&mut self, id: VariableId) {
+		self.heap.push(id.to_usize(), |i| self.q[i]);
+	}
+
+	fn conflicting(&mut self, id: VariableId) {
+		self.last_conflict[id.to_usize()] = self.conflicts;
+	}
+
+	fn bump(&mut self, _id: VariableId) {}
+
+	fn propagated(&mut self, conflict: bool) {
+		let multiplier = if conflict {
+			self.alpha
+		} else {
+			self.propagation_reward * self.alpha
+		};
+		let nalpha = 1.0 - self.alpha;
+		for id in self.plays.drain(..) {
+			let q = &mut self.q[id.to_usize()];
+			*q = nalpha * *q + multiplier / ((self.conflicts - self.last_conflict[id.to_usize()] + 1) as f64);
+			self.heap.update(id.to_usize(), |i| self.q[i]);
+		}
+		if conflict {
+			self.conflicts += 1;
+			if self.alpha > self.alpha_min {
+				self.alpha -= self.alpha_decay;
+			}
+		}
+	}
+
+	fn learned(&mut self) {}
+
+	fn next(&mut self, assignment: &Assignment) -> Option<VariableId> {
+		// assigned variables are only removed from the heap once they reach the top
+		while let Some(id) = self.heap.pop(|i| self.q[i]) {
+			if !assignment.has_value(VariableId::from_usize(id)) {
+				return Some(VariableId::from_usize(id));
+			}
+		}
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cnf::heuristic::BranchingHeuristic;
+
+	#[test]
+	fn rewards_conflicting_variables() {
+		let mut chb = Chb::new(&SolverConfig::default());
+		chb.initialize(&[0.0; 3]);
+		chb.propagated(true);
+		// only the variables of the conflicting clause count as part of the conflict, not every one seen in its analysis
+		chb.conflicting(VariableId::from_usize(0));
+		chb.bump(VariableId::from_usize(0));
+		chb.bump(VariableId::from_usize(1));
+		for id in 0..3 {
+			chb.assigned(VariableId::from_usize(id));
+		}
+		chb.propagated(false);
+		assert!(chb.q[0] > chb.q[1]);
+		assert_eq!(chb.q[1], chb.q[2]);
+	}
+}
@@ -0,0 +1,75 @@
+use crate::cnf::{Assignment, SolverConfig, VariableId};
+use crate::util::Heap;
+
+// activities are rescaled before they can overflow
+const RESCALE_LIMIT: f64 = 1e100;
+
+/// Exponential Variable State Independent Decaying Sum, which bumps the activity of each variable seen during conflict
+/// analysis by an increment that grows exponentially with the number of conflicts
+#[derive(Debug)]
+pub struct Evsids {
+	activity: Vec<f64>,
+	increment: f64,
+	decay: f64,
+	heap: Heap,
+}
+
+impl Evsids {
+	pub fn new(config: &SolverConfig) -> Evsids {
+		Evsids {
+			activity: Vec::new(),
+			increment: 1.0,
+			decay: config.vsids_decay,
+			heap: Heap::new(),
+		}
+	}
+}
+
+impl super::BranchingHeuristic for Evsids {
+	fn initialize(&mut self, scores: &[f64]) {
+		for &score in scores {
+			self.activity.push(score);
+			self.heap.push(self.activity.len() - 1, |i| self.activity[i]);
+		}
+	}
+
+	fn add_variable(&mut self) {
+		self.initialize(&[0.0]);
+	}
+
+	fn assigned(&mut self, _id: VariableId) {}
+
+	fn unassigned(&mut self, id: VariableId) {
+		self.heap.push(id.to_usize(), |i| self.activity[i]);
+	}
+
+	fn conflicting(&mut self, _id: VariableId) {}
+
+	fn bump(&mut self, id: VariableId) {
+		let activity = &mut self.activity[id.to_usize()];
+		*activity += self.increment;
+		if *activity > RESCALE_LIMIT {
+			// scaling all activities by the same factor does not change their order
+			for activity in self.activity.iter_mut() {
+				*activity /= RESCALE_LIMIT;
+			}
+			self.increment /= RESCALE_LIMIT;
+		}
+		self.heap.update(id.to_usize(), |i| self.activity[i]);
+	}
+
+	fn propagated(&mut self, _conflict: bool) {}
+
+	fn learned(&mut self) {
+		self.increment /= self.decay;
+	}
+
+	fn next(&mut self, assignment: &Assignment) -> Option<VariableId> {
+		while let Some(id) = self.heap.pop(|i| self.activity[i]) {
+			if !assignment.has_value(VariableId::from_usize(id)) {
+				return Some(VariableId::from_usize(id));
+			}
+		}
+		None
+	}
+}
@@ -0,0 +1,87 @@
+use crate::cnf::{Assignment, SolverConfig, VariableId};
+use crate::util::Heap;
+
+/// Learning Rate Based branching, which keeps an exponential recency weighted average of the participation rate of each
+/// variable, i.e., the fraction of the conflicts during an assignment of the variable in whose analysis it was seen
+#[derive(Debug)]
+pub struct Lrb {
+	alpha: f64,
+	alpha_decay: f64,
+	alpha_min: f64,
+	q: Vec<f64>,
+	// the number of learned clauses when the variable was assigned
+	assigned_at: Vec<u64>,
+	participated: Vec<u64>,
+	learned: u64,
+	heap: Heap,
+}
+
+impl Lrb {
+	pub fn new(config: &SolverConfig) -> Lrb {
+		Lrb {
+			alpha: config.alpha,
+			alpha_decay: config.alpha_decay,
+			alpha_min: config.alpha_min,
+			q: Vec::new(),
+			assigned_at: Vec::new(),
+			participated: Vec::new(),
+			learned: 0,
+			heap: Heap::new(),
+		}
+	}
+}
+
+impl super::BranchingHeuristic for Lrb {
+	fn initialize(&mut self, scores: &[f64]) {
+		for &score in scores {
+			self.q.push(score);
+			self.assigned_at.push(0);
+			self.participated.push(0);
+			self.heap.push(self.q.len() - 1, |i| self.q[i]);
+		}
+	}
+
+	fn add_variable(&mut self) {
+		self.initialize(&[0.0]);
+	}
+
+	fn assigned(&mut self, id: VariableId) {
+		self.assigned_at[id.to_usize()] = self.learned;
+		self.participated[id.to_usize()] = 0;
+	}
+
+	fn unassigned(&mut self, id: VariableId) {
+		let interval = self.learned - self.assigned_at[id.to_usize()];
+		if interval > 0 {
+			let rate = self.participated[id.to_usize()] as f64 / interval as f64;
+			let q = &mut self.q[id.to_usize()];
+			*q = (1.0 - self.alpha) * *q + self.alpha * rate;
+			self.heap.update(id.to_usize(), |i| self.q[i]);
+		}
+		self.heap.push(id.to_usize(), |i| self.q[i]);
+	}
+
+	fn conflicting(&mut self, _id: VariableId) {}
+
+	fn bump(&mut self, id: VariableId) {
+		self.participated[id.to_usize()] += 1;
+	}
+
+	fn propagated(&mut self, _conflict: bool) {}
+
+	fn learned(&mut self) {
+		self.learned += 1;
+		if self.alpha > self.alpha_min {
+			self.alpha -= self.alpha_decay;
+		}
+	}
+
+	fn next(&mut self, assignment: &Assignment) -> Option<VariableId> {
+		while let Some(id) = self.heap.pop(|i| self.q[i]) {
+			if !assignment.has_value(VariableId::from_usize(id)) {
+				return Some(VariableId::from_usize(id));
+			}
+		}
+		None
+	}
+}
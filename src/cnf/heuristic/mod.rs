@@ -0,0 +1,69 @@
+use std::fmt;
+
+use crate::cnf::{Assignment, SolverConfig, VariableId};
+
+mod chb;
+pub use self::chb::Chb;
+
+mod evsids;
+pub use self::evsids::Evsids;
+
+mod lrb;
+pub use self::lrb::Lrb;
+
+mod vmtf;
+pub use self::vmtf::Vmtf;
+
+/// The available implementations of `BranchingHeuristic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BranchingPolicy {
+	/// Conflict History-Based branching, which rewards variables for taking part in recent conflicts
+	Chb,
+	/// Exponential Variable State Independent Decaying Sum, which bumps the variables seen during conflict analysis
+	Evsids,
+	/// Variable Move-To-Front, which moves the variables seen during conflict analysis to the front of a queue
+	Vmtf,
+	/// Learning Rate Based branching, which rewards variables for taking part in conflicts while they are assigned
+	Lrb,
+}
+
+/// Chooses the decision variables of the search, based on the events that the solver reports.
+///
+/// Variables are identified by their index. Every variable that is assigned during the search is eventually reported
+/// as unassigned again, except for those that are assigned permanently at depth 0.
+pub trait BranchingHeuristic: fmt::Debug {
+	/// Adds the variables of a new problem, which are ranked by a static heuristic that gives each of them a score
+	/// between 0 and 1
+	fn initialize(&mut self, scores: &[f64]);
+
+	/// Adds a variable after the problem has been created
+	fn add_variable(&mut self);
+
+	fn assigned(&mut self, id: VariableId);
+
+	fn unassigned(&mut self, id: VariableId);
+
+	/// The variable occurs in the conflicting clause, which is reported before the analysis of the conflict starts
+	fn conflicting(&mut self, id: VariableId);
+
+	/// The variable was seen during the analysis of the current conflict
+	fn bump(&mut self, id: VariableId);
+
+	/// The propagation of the last assignment has finished
+	fn propagated(&mut self, conflict: bool);
+
+	/// The analysis of the current conflict has finished
+	fn learned(&mut self);
+
+	/// Returns the next decision variable, which must be unassigned, or `None` if all variables are assigned
+	fn next(&mut self, assignment: &Assignment) -> Option<VariableId>;
+}
+
+pub(crate) fn create(config: &SolverConfig) -> Box<dyn BranchingHeuristic> {
+	match config.branching {
+		BranchingPolicy::Chb => Box::new(Chb::new(config)),
+		BranchingPolicy::Evsids => Box::new(Evsids::new(config)),
+		BranchingPolicy::Vmtf => Box::new(Vmtf::new()),
+		BranchingPolicy::Lrb => Box::new(Lrb::new(config)),
+	}
+}
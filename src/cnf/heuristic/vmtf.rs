@@ -0,0 +1,120 @@
+use crate::cnf::{Assignment, VariableId};
+
+// the end of the queue
+const NONE: usize = usize::MAX;
+
+/// Variable Move-To-Front, which keeps the variables in a queue and moves those seen during conflict analysis to its
+/// front. Decisions are made on the unassigned variable closest to the front.
+#[derive(Debug)]
+pub struct Vmtf {
+	// towards the back and the front of the queue, respectively
+	prev: Vec<usize>,
+	next: Vec<usize>,
+	// the order in which the variables were enqueued, which increases towards the front
+	stamps: Vec<u64>,
+	stamp: u64,
+	front: usize,
+	// no variable in front of this one is unassigned
+	search: usize,
+	bumped: Vec<VariableId>,
+}
+
+impl Vmtf {
+	pub fn new() -> Vmtf {
+		Vmtf {
+			prev: Vec::new(),
+			next: Vec::new(),
+			stamps: Vec::new(),
+			stamp: 0,
+			front: NONE,
+			search: NONE,
+			bumped: Vec::new(),
+		}
+	}
+
+	fn dequeue(&mut self, id: usize) {
+		let (prev, next) = (self.prev[id], self.next[id]);
+		if prev != NONE {
+			self.next[prev] = next;
+		}
+		if next != NONE {
+			self.prev[next] = prev;
+		} else {
+			self.front = prev;
+		}
+	}
+
+	fn enqueue(&mut self, id: usize) {
+		self.prev[id] = self.front;
+		self.next[id] = NONE;
+		if self.front != NONE {
+			self.next[self.front] = id;
+		}
+		self.front = id;
+		self.stamp += 1;
+		self.stamps[id] = self.stamp;
+	}
+}
+
+impl Default for Vmtf {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl super::BranchingHeuristic for Vmtf {
+	fn initialize(&mut self, scores: &[f64]) {
+		let start = self.stamps.len();
+		let mut order: Vec<usize> = (start..start + scores.len()).collect();
+		order.sort_by(|&a, &b| scores[a - start].total_cmp(&scores[b - start]));
+		self.prev.resize(start + scores.len(), NONE);
+		self.next.resize(start + scores.len(), NONE);
+		self.stamps.resize(start + scores.len(), 0);
+		for id in order {
+			self.enqueue(id);
+		}
+		self.search = self.front;
+	}
+
+	fn add_variable(&mut self) {
+		self.initialize(&[0.0]);
+	}
+
+	fn assigned(&mut self, _id: VariableId) {}
+
+	fn unassigned(&mut self, id: VariableId) {
+		if self.search == NONE || self.stamps[id.to_usize()] > self.stamps[self.search] {
+			self.search = id.to_usize();
+		}
+	}
+
+	fn conflicting(&mut self, _id: VariableId) {}
+
+	fn bump(&mut self, id: VariableId) {
+		self.bumped.push(id);
+	}
+
+	fn propagated(&mut self, _conflict: bool) {}
+
+	fn learned(&mut self) {
+		// keep the relative order of the bumped variables
+		let mut bumped = std::mem::take(&mut self.bumped);
+		bumped.sort_unstable_by_key(|id| self.stamps[id.to_usize()]);
+		for id in bumped.drain(..) {
+			if self.front != id.to_usize() {
+				self.dequeue(id.to_usize());
+				self.enqueue(id.to_usize());
+			}
+		}
+		self.bumped = bumped;
+	}
+
+	fn next(&mut self, assignment: &Assignment) -> Option<VariableId> {
+		let mut id = self.search;
+		while id != NONE && assignment.has_value(VariableId::from_usize(id)) {
+			id = self.prev[id];
+		}
+		self.search = id;
+		(id != NONE).then(|| VariableId::from_usize(id))
+	}
+}
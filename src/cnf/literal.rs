@@ -8,10 +8,22 @@ mod literal_impl {
 
 	static_assertions::const_assert!(u32::BITS <= usize::BITS);
 
-	#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+	#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 	pub struct Literal(u32);
 
 	impl Literal {
+		pub(crate) const BITS: u32 = u32::BITS;
+
+		#[inline]
+		pub(crate) const fn from_raw(raw: usize) -> Literal {
+			Self(raw as u32)
+		}
+
+		#[inline]
+		pub(crate) const fn to_raw(self) -> usize {
+			self.0 as usize
+		}
+
 		#[inline]
 		pub fn new(id: VariableId, negated: bool) -> Literal {
 			let id = id.as_raw();
@@ -35,10 +47,22 @@ mod literal_impl {
 mod literal_impl {
 	use super::*;
 
-	#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+	#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 	pub struct Literal(usize);
 
 	impl Literal {
+		pub(crate) const BITS: u32 = usize::BITS;
+
+		#[inline]
+		pub(crate) const fn from_raw(raw: usize) -> Literal {
+			Self(raw)
+		}
+
+		#[inline]
+		pub(crate) const fn to_raw(self) -> usize {
+			self.0
+		}
+
 		#[inline]
 		pub fn new(id: VariableId, negated: bool) -> Literal {
 			let id = id.as_raw();
@@ -61,6 +85,18 @@ mod literal_impl {
 pub use literal_impl::Literal;
 
 impl Literal {
+	/// The position of the literal in arrays that hold one entry per literal
+	#[inline]
+	pub fn index(&self) -> usize {
+		self.to_raw()
+	}
+
+	/// The literal of the same variable with the opposite polarity
+	#[inline]
+	pub fn negate(&self) -> Literal {
+		Literal::from_raw(self.to_raw() ^ 1)
+	}
+
 	#[inline]
 	pub fn disassemble(&self) -> (VariableId, bool) {
 		(self.id(), self.negated())
@@ -103,6 +139,8 @@ mod tests {
 		let lit = Literal::new(VariableId::from_usize(13), false);
 		assert_eq!(lit.id().to_usize(), 13);
 		assert!(!lit.negated());
+		assert_eq!(lit.negate(), Literal::new(lit.id(), true));
+		assert_eq!(lit.negate().negate(), lit);
 	}
 
 	#[test]
@@ -1,19 +1,36 @@
 mod literal;
 pub use self::literal::Literal;
 
+mod assignment;
+pub use self::assignment::Assignment;
+
 pub mod clause;
-pub use self::clause::{Clause, ClauseLiteralVec};
+pub use self::clause::{ArenaRef, ClauseArena, ClauseLiteralVec, ClauseRef};
+
+pub mod config;
+pub use self::config::{RestartPolicy, SolverConfig};
+
+pub mod errors;
+
+pub mod heuristic;
+pub use self::heuristic::{BranchingHeuristic, BranchingPolicy};
 
 pub mod problem;
 pub use self::problem::Problem;
 
+pub mod proof;
+pub use self::proof::{Proof, ProofFormat};
+
+mod statistics;
+pub use self::statistics::Statistics;
+
 mod problembuilder;
 pub use self::problembuilder::ProblemBuilder;
 
 pub mod util;
 
 pub mod variable;
-pub use self::variable::{Variable, VariableId};
+pub use self::variable::{Implication, Variable, VariableId, Watcher};
 
 #[cfg(test)]
 mod tests;
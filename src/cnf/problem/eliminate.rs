@@ -0,0 +1,231 @@
+use crate::SolverResult;
+use crate::cnf::{Assignment, ClauseLiteralVec, Literal, Proof, VariableId};
+use crate::util::Marks;
+
+// variables with more occurrences are not eliminated, as the number of resolutions grows quadratically with them
+const MAX_OCCURRENCES: usize = 100;
+// resolvents that are longer than this are considered to make the problem harder rather than easier
+const MAX_RESOLVENT_LEN: usize = 20;
+
+/// The clauses that were removed by eliminating or substituting variables, or for being blocked, which are needed to
+/// extend a model of the remaining clauses to a model of all clauses
+#[derive(Debug, Clone, Default)]
+pub(super) struct Reconstruction {
+	// each removed clause together with the literal that may be flipped to satisfy it and its proof id, in the order
+	// of removal
+	stack: Vec<(Literal, ClauseLiteralVec, u64)>,
+	eliminated: Vec<bool>,
+	// the variables that are the witness of at least one clause on the stack
+	witnesses: Vec<bool>,
+}
+
+impl Reconstruction {
+	pub fn new(variables: usize) -> Reconstruction {
+		Reconstruction {
+			stack: Vec::new(),
+			eliminated: vec![false; variables],
+			witnesses: vec![false; variables],
+		}
+	}
+
+	pub fn add_variable(&mut self) {
+		self.eliminated.push(false);
+		self.witnesses.push(false);
+	}
+
+	pub fn is_eliminated(&self, id: VariableId) -> bool {
+		self.eliminated[id.to_usize()]
+	}
+
+	/// Whether clauses that were removed on behalf of the variable `id` can be restored
+	pub fn is_witness(&self, id: VariableId) -> bool {
+		self.witnesses[id.to_usize()]
+	}
+
+	/// Records the removed `clause`, which is satisfied by flipping `witness` when no other literal satisfies it. The
+	/// variable of `witness` is either eliminated, or every remaining clause containing its negation contains the
+	/// negation of another literal of `clause` as well. A proof has to keep the clause `id`, as it is needed again
+	/// once the clause is restored.
+	pub fn push(&mut self, witness: Literal, clause: ClauseLiteralVec, id: u64) {
+		debug_assert!(clause.contains(&witness));
+		self.witnesses[witness.id().to_usize()] = true;
+		self.stack.push((witness, clause, id));
+	}
+
+	/// Records that the variable of `lit` was replaced by the equivalent literal `representative`, which may be
+	/// eliminated later on. `ids` are the proof ids of the clauses {lit, ¬representative} and {¬lit, representative}.
+	pub fn push_equivalence(&mut self, lit: Literal, representative: Literal, ids: [u64; 2]) {
+		self.push(
+			lit,
+			ClauseLiteralVec::from_slice(&[lit, representative.negate()]),
+			ids[0],
+		);
+		self.push(
+			lit.negate(),
+			ClauseLiteralVec::from_slice(&[lit.negate(), representative]),
+			ids[1],
+		);
+		self.eliminated[lit.id().to_usize()] = true;
+	}
+
+	/// Changes the arbitrary values of the eliminated variables and flips witnesses of blocked clauses where necessary,
+	/// so that the removed clauses are satisfied as well. All other variables must already be assigned.
+	pub fn extend(&self, assignment: &mut Assignment) {
+		for (witness, clause, _) in self.stack.iter().rev() {
+			if !clause.iter().any(|&lit| assignment.literal_value(lit) == Some(true)) {
+				assignment.set(witness.id(), !witness.negated(), VariableId::from_usize(0), None);
+			}
+		}
+	}
+
+	/// Takes back the clauses that were removed on behalf of `id` together with their proof ids, after which the
+	/// variable counts as active again. The clauses may contain variables that were eliminated later on.
+	pub fn restore(&mut self, id: VariableId) -> Vec<(ClauseLiteralVec, u64)> {
+		self.eliminated[id.to_usize()] = false;
+		self.witnesses[id.to_usize()] = false;
+		let (restored, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.stack)
+			.into_iter()
+			.partition(|(witness, _, _)| witness.id() == id);
+		self.stack = kept;
+		restored.into_iter().map(|(_, clause, id)| (clause, id)).collect()
+	}
+}
+
+// SatELite-style bounded variable elimination: each variable is replaced by all non-tautological resolvents of its
+// positive and negative occurrences, as long as this does not increase the number of clauses. The clauses have to be
+// sorted, and are kept parallel to `ids` like in `precompute`. Eliminated variables are assigned an arbitrary value, as
+// they do not occur in the remaining clauses anymore, which `reconstruction` corrects once a model is found. The proof
+// keeps the removed clauses, since incremental calls may restore them. Resolvents may be units, so the result should
+// be propagated once more.
+pub fn eliminate(
+	assignment: &mut Assignment,
+	frozen: &[VariableId],
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	next_id: &mut u64,
+	proof: &mut Option<Proof>,
+	reconstruction: &mut Reconstruction,
+) -> SolverResult {
+	debug_assert_eq!(clauses.len(), ids.len());
+	// occurrence lists, indexed by literal, which may still refer to removed clauses
+	let mut occurrences = vec![Vec::new(); 2 * assignment.len()];
+	for (ci, clause) in clauses.iter().enumerate() {
+		debug_assert!(clause.windows(2).all(|pair| pair[0] < pair[1]));
+		for lit in clause.iter() {
+			occurrences[lit.index()].push(ci);
+		}
+	}
+	let mut removed = vec![false; clauses.len()];
+	let mut skipped = Marks::new();
+	for id in frozen {
+		skipped.insert(id.to_usize());
+	}
+
+	let mut candidates: Vec<VariableId> = (0..assignment.len()).map(VariableId::from_usize).collect();
+	// the variables whose occurrences changed during the current round, which are the candidates of the next one
+	let mut touched = Marks::new();
+	let mut next = Vec::new();
+	let mut marks = Marks::new();
+	let mut resolvents = Vec::new();
+	while !candidates.is_empty() {
+		// variables with few occurrences are cheap to eliminate and are therefore tried first
+		candidates.retain(|&id| !assignment.has_value(id) && !skipped.contains(id.to_usize()));
+		candidates.sort_by_cached_key(|&id| {
+			occurrences[Literal::new(id, false).index()].len() + occurrences[Literal::new(id, true).index()].len()
+		});
+		for &id in candidates.iter() {
+			let (pos, neg) = (Literal::new(id, false).index(), Literal::new(id, true).index());
+			occurrences[pos].retain(|&ci| !removed[ci]);
+			occurrences[neg].retain(|&ci| !removed[ci]);
+			let bound = occurrences[pos].len() + occurrences[neg].len();
+			if bound > MAX_OCCURRENCES {
+				continue;
+			}
+			resolvents.clear();
+			let mut bounded = true;
+			'resolution: for &pi in occurrences[pos].iter() {
+				for &ni in occurrences[neg].iter() {
+					if let Some(resolvent) = resolve(&clauses[pi], &clauses[ni], id, &mut marks) {
+						if resolvent.len() > MAX_RESOLVENT_LEN || resolvents.len() == bound {
+							bounded = false;
+							break 'resolution;
+						}
+						resolvents.push((resolvent, [ids[pi], ids[ni]]));
+					}
+				}
+			}
+			if !bounded {
+				continue;
+			}
+
+			for (resolvent, hints) in resolvents.drain(..) {
+				let rid = *next_id;
+				*next_id += 1;
+				if let Some(proof) = proof.as_mut() {
+					proof.add(rid, resolvent.iter().copied(), hints);
+				}
+				if resolvent.is_empty() {
+					return SolverResult::Unsat;
+				}
+				for lit in resolvent.iter() {
+					occurrences[lit.index()].push(clauses.len());
+					if touched.insert(lit.id().to_usize()) {
+						next.push(lit.id());
+					}
+				}
+				clauses.push(resolvent);
+				ids.push(rid);
+				removed.push(false);
+			}
+			for ci in std::mem::take(&mut occurrences[pos])
+				.into_iter()
+				.chain(std::mem::take(&mut occurrences[neg]))
+			{
+				removed[ci] = true;
+				let clause = std::mem::take(&mut clauses[ci]);
+				for lit in clause.iter() {
+					if touched.insert(lit.id().to_usize()) {
+						next.push(lit.id());
+					}
+				}
+				let witness = *clause
+					.iter()
+					.find(|lit| lit.id() == id)
+					.expect("the clause contains the variable");
+				reconstruction.push(witness, clause, ids[ci]);
+			}
+			assignment.set(id, false, VariableId::from_usize(0), None);
+			reconstruction.eliminated[id.to_usize()] = true;
+		}
+		// only variables whose occurrences changed can have become eliminable
+		std::mem::swap(&mut candidates, &mut next);
+		next.clear();
+		touched.clear();
+	}
+
+	super::retain(clauses, ids, &removed);
+	if clauses.is_empty() {
+		SolverResult::Sat
+	} else {
+		SolverResult::Unknown
+	}
+}
+
+// the sorted resolvent of `positive` and `negative` on `id`, or `None` if it is a tautology
+fn resolve(positive: &[Literal], negative: &[Literal], id: VariableId, marks: &mut Marks) -> Option<ClauseLiteralVec> {
+	let mut resolvent: ClauseLiteralVec = positive.iter().copied().filter(|lit| lit.id() != id).collect();
+	marks.clear();
+	for lit in resolvent.iter() {
+		marks.insert(lit.index());
+	}
+	for &lit in negative.iter().filter(|lit| lit.id() != id) {
+		if marks.contains(lit.negate().index()) {
+			return None;
+		}
+		if marks.insert(lit.index()) {
+			resolvent.push(lit);
+		}
+	}
+	resolvent.sort();
+	Some(resolvent)
+}
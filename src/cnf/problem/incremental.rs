@@ -0,0 +1,128 @@
+use std::fmt;
+
+use crate::SolverResult;
+use crate::cnf::{ClauseLiteralVec, Problem, Variable, VariableId};
+
+impl<T: fmt::Display> Problem<T> {
+	/// Adds a new, unconstrained variable called `name` to the problem
+	pub fn add_variable(&mut self, name: T) -> VariableId {
+		self.restart();
+		let id = VariableId::from_usize(self.variables.len());
+		self.variables.push(Variable::new());
+		self.assignment.add_variable();
+		self.reconstruction.add_variable();
+		self.heuristic.add_variable();
+		self.variable_names.push(name);
+		self.unit_ids.push(0);
+		self.active_variables += 1;
+		id
+	}
+
+	/// Adds an irreducible clause to the problem. Learned clauses are kept, as they are still implied by the extended
+	/// problem.
+	///
+	/// The clause receives the next free proof id, as if it had been part of the input. Since derived clauses are
+	/// numbered from the same counter, an LRAT proof of an incrementally extended problem can not be checked against
+	/// a plain dimacs file.
+	pub fn add_clause(&mut self, literals: ClauseLiteralVec) {
+		if self.solution == SolverResult::Unsat {
+			return;
+		}
+		self.restart();
+		debug_assert!(literals.iter().all(|lit| lit.id().to_usize() < self.variables.len()));
+		if let Some(original) = self.original_clauses.as_mut() {
+			original.push(literals.clone());
+		}
+		for lit in literals.iter() {
+			self.reactivate(lit.id());
+		}
+		let id = self.new_clause_id();
+		self.insert_clause(literals, id);
+	}
+
+	/// Makes the eliminated variable `id` part of the search again by restoring the clauses it was eliminated with,
+	/// which is necessary before it is used by new clauses or assumptions. Blocked clauses that `id` is the witness of
+	/// are restored as well, as they may not be blocked anymore. Does nothing for other variables.
+	pub(super) fn reactivate(&mut self, id: VariableId) {
+		let eliminated = self.reconstruction.is_eliminated(id);
+		if !eliminated && !self.reconstruction.is_witness(id) {
+			return;
+		}
+		debug_assert_eq!(self.depth.to_usize(), 0);
+		let clauses = self.reconstruction.restore(id);
+		if eliminated {
+			self.assignment.unset(id);
+			self.heuristic.unassigned(id);
+			self.active_variables += 1;
+			self.stats.eliminated_variables -= 1;
+		}
+		for (clause, original) in clauses {
+			// the proof still has the original clause, of which a copy is added, so that the proof refers to the
+			// restored clause by the same id as the solver
+			let id = self.new_clause_id();
+			if let Some(proof) = self.proof.as_mut() {
+				proof.add(id, clause.iter().copied(), [original]);
+				proof.delete(original, clause.iter().copied());
+			}
+			self.insert_clause(clause, id);
+		}
+	}
+
+	// adds the clause `id` after the restart, without recording it as an original clause
+	fn insert_clause(&mut self, mut literals: ClauseLiteralVec, mut id: u64) {
+		if self.solution == SolverResult::Unsat {
+			return;
+		}
+		for lit in literals.iter() {
+			self.reactivate(lit.id());
+		}
+
+		literals.sort();
+		literals.dedup();
+		if literals.windows(2).any(|pair| pair[0].id() == pair[1].id()) {
+			// contains both x and ¬x
+			return;
+		}
+		// after the restart, only the permanent assignments of depth 0 remain
+		let mut removed = Vec::new();
+		let mut satisfied = false;
+		literals.retain(|lit| match self.assignment.literal_value(*lit) {
+			None => true,
+			Some(value) => {
+				satisfied |= value;
+				removed.push(self.unit_ids[lit.id().to_usize()]);
+				false
+			}
+		});
+		if satisfied {
+			return;
+		}
+		if !removed.is_empty() && self.proof.is_some() {
+			let strengthened = self.new_clause_id();
+			removed.push(id);
+			if let Some(proof) = self.proof.as_mut() {
+				proof.add(strengthened, literals.iter().copied(), removed);
+			}
+			id = strengthened;
+		}
+
+		match literals.len() {
+			0 => self.solution = SolverResult::Unsat,
+			1 => {
+				if let Some(cid) = self.propagate_unit(literals[0], id) {
+					self.log_empty_clause(cid);
+					self.solution = SolverResult::Unsat;
+				}
+			}
+			2 => self.add_binary_clause([literals[0], literals[1]], id),
+			_ => {
+				let cref = self
+					.clauses
+					.push(id, &literals, VariableId::from_usize(1), false, [0, 1]);
+				self
+					.clauses
+					.initialize_watched(cref, &self.assignment, &mut self.variables);
+			}
+		}
+	}
+}
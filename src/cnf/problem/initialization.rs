@@ -2,56 +2,203 @@ use std::collections::HashMap;
 use std::fmt;
 
 use crate::SolverResult;
-use crate::cnf::{Clause, ClauseLiteralVec, Problem, Variable, VariableId};
-use crate::util::Histo;
+use crate::cnf::{Assignment, ClauseArena, ClauseLiteralVec, Problem, Proof, SolverConfig, Variable, VariableId};
+use crate::util::{Histo, Marks, Stamps, Stopwatch};
 
 impl<T: fmt::Display> Problem<T> {
-	pub fn new(names: Vec<T>, mut clauses: Vec<ClauseLiteralVec>) -> Problem<T> {
+	pub fn new(names: Vec<T>, clauses: Vec<ClauseLiteralVec>) -> Problem<T> {
+		Self::build(names, clauses, None, false, &[], SolverConfig::default())
+	}
+
+	/// Creates a problem whose search is tuned by `config` instead of the default parameters
+	pub fn with_config(names: Vec<T>, clauses: Vec<ClauseLiteralVec>, config: SolverConfig) -> Problem<T> {
+		Self::build(names, clauses, None, false, &[], config)
+	}
+
+	/// Creates a problem that writes a proof for unsatisfiable results, starting with the preprocessing
+	pub fn with_proof(names: Vec<T>, clauses: Vec<ClauseLiteralVec>, proof: Proof) -> Problem<T> {
+		Self::build(names, clauses, Some(proof), false, &[], SolverConfig::default())
+	}
+
+	pub(crate) fn build(
+		names: Vec<T>,
+		mut clauses: Vec<ClauseLiteralVec>,
+		mut proof: Option<Proof>,
+		keep_original: bool,
+		frozen: &[VariableId],
+		config: SolverConfig,
+	) -> Problem<T> {
+		let mut stopwatch = Stopwatch::new();
+		let original_clauses = keep_original.then(|| clauses.clone());
 		let varcount = names.len();
-		let mut variables = (0..varcount).map(|_| Variable::new()).collect();
-		let solution = super::precompute::precompute(&mut variables, &mut clauses);
-		let irreducible = clauses.len();
-		let last_conflict = vec![0; varcount];
-		let active_variables = variables.iter().filter(|var| !var.has_value()).count();
+		let variables: Vec<Variable> = (0..varcount).map(|_| Variable::new()).collect();
+		let mut assignment = Assignment::new(varcount);
+		// input clauses are numbered from 1 in the order they were given, as expected by LRAT proofs
+		let mut ids: Vec<u64> = (1..=clauses.len() as u64).collect();
+		let mut unit_ids = vec![0; varcount];
+		let mut next_clause_id = clauses.len() as u64 + 1;
+		let mut solution = super::precompute::precompute(
+			&mut assignment,
+			&mut clauses,
+			&mut ids,
+			&mut unit_ids,
+			&mut next_clause_id,
+			&mut proof,
+		);
+		let mut reconstruction = super::eliminate::Reconstruction::new(varcount);
+		let mut subsumption = (0, 0);
+		let (mut equivalent_variables, mut transitive_binaries) = (0, 0);
+		let (mut tautologies, mut blocked_clauses) = (0, 0);
+		if solution != SolverResult::Unsat && config.substitute {
+			solution = super::substitute::substitute(
+				&mut assignment,
+				frozen,
+				&mut clauses,
+				&mut ids,
+				&mut next_clause_id,
+				&mut proof,
+				&mut reconstruction,
+			);
+			equivalent_variables = (0..varcount)
+				.filter(|&id| reconstruction.is_eliminated(VariableId::from_usize(id)))
+				.count() as u64;
+			if solution != SolverResult::Unsat {
+				transitive_binaries = super::substitute::reduce_transitively(varcount, &mut clauses, &mut ids, &mut proof);
+			}
+		}
+		if solution != SolverResult::Unsat {
+			subsumption = super::subsume::subsume(varcount, &mut clauses, &mut ids, &mut next_clause_id, &mut proof);
+			if config.eliminate_tautologies {
+				tautologies = super::precompute::eliminate_tautologies(varcount, &mut clauses, &mut ids, &mut proof);
+			}
+			if config.eliminate_blocked {
+				blocked_clauses =
+					super::precompute::eliminate_blocked(varcount, frozen, &mut clauses, &mut ids, &mut reconstruction);
+			}
+			if config.eliminate {
+				solution = super::eliminate::eliminate(
+					&mut assignment,
+					frozen,
+					&mut clauses,
+					&mut ids,
+					&mut next_clause_id,
+					&mut proof,
+					&mut reconstruction,
+				);
+			}
+			if solution != SolverResult::Unsat {
+				// substituted and strengthened clauses and resolvents may be unit clauses
+				solution = super::precompute::precompute(
+					&mut assignment,
+					&mut clauses,
+					&mut ids,
+					&mut unit_ids,
+					&mut next_clause_id,
+					&mut proof,
+				);
+			}
+		}
+		let eliminated_variables = (0..varcount)
+			.filter(|&id| reconstruction.is_eliminated(VariableId::from_usize(id)))
+			.count();
+		if solution == SolverResult::Unsat {
+			// the remaining clauses may be in an inconsistent state and are not needed anymore
+			clauses.clear();
+			ids.clear();
+		}
+		// binary clauses are only kept in the implication lists of their literals
+		let (binary, long): (Vec<_>, Vec<_>) = clauses.into_iter().zip(ids).partition(|(c, _)| c.len() == 2);
+		let mut arena = ClauseArena::new();
+		for (c, id) in long {
+			arena.push(id, &c, VariableId::from_usize(1), false, [0, 1]);
+		}
+		let active_variables = (0..varcount)
+			.filter(|&id| !assignment.has_value(VariableId::from_usize(id)))
+			.count();
 		let mut problem = Problem {
-			alpha: 0.4,
-			gc_count: 0,
+			restarts: super::restart::Restarts::new(&config),
+			heuristic: crate::cnf::heuristic::create(&config),
+			config,
 			variables,
+			assignment,
 			variable_names: names,
-			clauses: clauses
-				.into_iter()
-				.map(|c| Clause::new(c, VariableId::from_usize(1)))
-				.collect(),
+			clauses: arena,
+			tiers: super::reduce::Tiers::new(),
 			applications: Vec::with_capacity(varcount),
-			irreducible,
-			num_conflicts: 0,
-			last_conflict,
-			plays: Vec::with_capacity(varcount),
+			reconstruction,
+			probing: Default::default(),
+			marks: Marks::new(),
+			depth_stamps: Stamps::new(),
+			analysis_queue: Vec::new(),
 			depth: VariableId::from_usize(0),
 			active_variables,
 			conflict_lens: Histo::new(),
+			stats: Default::default(),
+			failed_assumptions: Vec::new(),
+			next_clause_id,
+			unit_ids,
+			proof,
+			limits: Default::default(),
+			original_clauses,
 			solution,
 		};
-		if problem.solution == SolverResult::Unknown {
-			problem.initialize();
+		for (c, id) in binary {
+			problem.add_binary_clause([c[0], c[1]], id);
 		}
+		if problem.solution != SolverResult::Unsat {
+			// a trivially satisfiable problem is still searched, so that later assumptions are respected
+			problem.solution = SolverResult::Unknown;
+			let scores = problem.initialize();
+			problem.heuristic.initialize(&scores);
+			if problem.config.probe {
+				if let Some(conflict) = problem.probe() {
+					problem.log_empty_clause(conflict);
+					problem.solution = SolverResult::Unsat;
+				}
+			}
+		} else {
+			problem.heuristic.initialize(&vec![0.0; varcount]);
+		}
+		stopwatch.stop();
+		problem.stats.preprocessing_time = stopwatch.elapsed();
+		problem.stats.eliminated_variables = eliminated_variables as u64;
+		(problem.stats.subsumed_clauses, problem.stats.strengthened_clauses) = subsumption;
+		problem.stats.equivalent_variables = equivalent_variables;
+		problem.stats.transitive_binaries = transitive_binaries;
+		problem.stats.tautologies = tautologies;
+		problem.stats.blocked_clauses = blocked_clauses;
 		problem
 	}
 
-	fn initialize(&mut self) {
-		let mut counters = Vec::<[HashMap<i32, usize>; 2]>::with_capacity(self.variables.len().try_into().unwrap());
+	// initializes the watches and phases, and returns the initial scores of the variables for the branching heuristic
+	fn initialize(&mut self) -> Vec<f64> {
+		let mut scores = vec![0.0; self.variables.len()];
+		let mut counters = Vec::<[HashMap<i32, usize>; 2]>::with_capacity(self.variables.len());
 		for _ in 0..self.variables.len() {
 			counters.push([HashMap::new(), HashMap::new()]);
 		}
-		for i in 0..self.clauses.len() {
-			let len = self.clauses[i].len();
-			for (id, negated) in self.clauses[i].iter().map(|lit| lit.disassemble()) {
+		let crefs: Vec<_> = self.clauses.iter().collect();
+		for cref in crefs {
+			let literals = self.clauses.literals(cref);
+			let len = literals.len();
+			for (id, negated) in literals.iter().map(|lit| lit.disassemble()) {
 				*counters[id.to_usize()][negated as usize].entry(len as i32).or_insert(0) += 1; // FIXME: this cast is only mostly safe
 			}
-			self.clauses[i].initialize_watched(i, &mut self.variables);
+			self
+				.clauses
+				.initialize_watched(cref, &self.assignment, &mut self.variables);
+		}
+		for (id, var) in self.variables.iter().enumerate() {
+			for negated in [false, true] {
+				let count = var.get_implications(negated).len();
+				if count != 0 {
+					*counters[id][negated as usize].entry(2).or_insert(0) += count;
+				}
+			}
 		}
 		for (id, count) in counters.iter_mut().enumerate() {
-			if !self.variables[id].has_value() {
+			let id = VariableId::from_usize(id);
+			if !self.assignment.has_value(id) {
 				let lo: f64 = {
 					let mut vec: Vec<f64> = count[0]
 						.drain()
@@ -68,19 +215,20 @@ impl<T: fmt::Display> Problem<T> {
 					vec.sort_by(|a, b| a.partial_cmp(b).unwrap());
 					vec.iter().sum()
 				};
-				self.variables[id].set_phase(lo < hi);
-				*self.variables[id].q_mut() = lo + hi;
+				self.assignment.set_phase(id, lo < hi);
+				scores[id.to_usize()] = lo + hi;
 			}
 		}
-		let m: f64 = *self
-			.variables
+		let m: f64 = scores
 			.iter()
-			.filter(|var| !var.has_value())
-			.map(|v| v.q())
+			.copied()
 			.max_by(|a, b| a.partial_cmp(b).unwrap())
-			.unwrap();
-		for v in self.variables.iter_mut() {
-			*v.q_mut() /= m;
+			.unwrap_or(0.0);
+		if m > 0.0 {
+			for score in scores.iter_mut() {
+				*score /= m;
+			}
 		}
+		scores
 	}
 }
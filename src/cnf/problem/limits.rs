@@ -0,0 +1,84 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::cnf::Problem;
+
+/// Limits on the effort spent by a single call to `solve`, after which it gives up and returns `Unknown`
+#[derive(Debug, Clone, Default)]
+pub(super) struct Limits {
+	conflicts: Option<u64>,
+	propagations: Option<u64>,
+	time: Option<Duration>,
+	terminate: Option<Arc<AtomicBool>>,
+}
+
+// the limits of the current call to `solve` as absolute values
+#[derive(Debug)]
+pub(super) struct Budget {
+	conflicts: u64,
+	propagations: u64,
+	deadline: Option<Instant>,
+	polls: u32,
+}
+
+// reading the clock is comparatively expensive, so it is only done every so often
+const POLLS_PER_CLOCK: u32 = 64;
+
+impl<T: fmt::Display> Problem<T> {
+	/// Limits the number of conflicts that each call to `solve` may encounter
+	pub fn set_conflict_limit(&mut self, limit: Option<u64>) {
+		self.limits.conflicts = limit;
+	}
+
+	/// Limits the number of assignments that each call to `solve` may propagate
+	pub fn set_propagation_limit(&mut self, limit: Option<u64>) {
+		self.limits.propagations = limit;
+	}
+
+	/// Limits the wall-clock time that each call to `solve` may take
+	pub fn set_time_limit(&mut self, limit: Option<Duration>) {
+		self.limits.time = limit;
+	}
+
+	/// Makes `solve` return `Unknown` soon after `flag` is set, e.g., from another thread or a signal handler. The solver
+	/// never resets the flag, so it has to be cleared before solving can be resumed.
+	pub fn set_terminate_flag(&mut self, flag: Option<Arc<AtomicBool>>) {
+		self.limits.terminate = flag;
+	}
+
+	pub(super) fn budget(&self) -> Budget {
+		Budget {
+			conflicts: self
+				.limits
+				.conflicts
+				.map_or(u64::MAX, |limit| self.stats.conflicts.saturating_add(limit)),
+			propagations: self
+				.limits
+				.propagations
+				.map_or(u64::MAX, |limit| self.stats.propagations.saturating_add(limit)),
+			deadline: self.limits.time.and_then(|limit| Instant::now().checked_add(limit)),
+			polls: 0,
+		}
+	}
+
+	pub(super) fn is_exhausted(&self, budget: &mut Budget) -> bool {
+		if self.stats.conflicts >= budget.conflicts || self.stats.propagations >= budget.propagations {
+			return true;
+		}
+		if let Some(flag) = &self.limits.terminate {
+			if flag.load(Ordering::Relaxed) {
+				return true;
+			}
+		}
+		if let Some(deadline) = budget.deadline {
+			budget.polls += 1;
+			if budget.polls >= POLLS_PER_CLOCK {
+				budget.polls = 0;
+				return Instant::now() >= deadline;
+			}
+		}
+		false
+	}
+}
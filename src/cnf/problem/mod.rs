@@ -1,39 +1,110 @@
-use std::fmt;
+use std::{fmt, io};
 
 use crate::SolverResult;
-use crate::cnf::{Clause, Variable, VariableId};
-use crate::util::Histo;
+use crate::cnf::heuristic::BranchingHeuristic;
+use crate::cnf::{
+	Assignment, ClauseArena, ClauseLiteralVec, ClauseRef, Literal, Proof, SolverConfig, Statistics, Variable, VariableId,
+};
+use crate::util::{Histo, Marks, Stamps};
 
+mod eliminate;
+mod incremental;
 mod initialization;
+mod limits;
 mod precompute;
 mod print;
+mod probe;
+mod reduce;
+mod restart;
 mod solve;
+mod substitute;
+mod subsume;
 
 #[derive(Debug)]
 pub struct Problem<T: fmt::Display> {
-	alpha: f64,
-	gc_count: u64,
+	config: SolverConfig,
 	variables: Vec<Variable>,
+	assignment: Assignment,
 	variable_names: Vec<T>,
-	clauses: Vec<Clause>,
+	clauses: ClauseArena,
+	tiers: reduce::Tiers,
 	applications: Vec<VariableId>,
-	irreducible: usize,
-	num_conflicts: u64,
-	last_conflict: Vec<u64>,
-	plays: Vec<VariableId>,
+	reconstruction: eliminate::Reconstruction,
+	probing: probe::Probing,
+	// scratch space of the conflict analysis, which is kept to avoid allocations that grow with the problem size
+	marks: Marks,
+	depth_stamps: Stamps,
+	analysis_queue: Vec<ClauseRef>,
+	heuristic: Box<dyn BranchingHeuristic>,
 	depth: VariableId,
 	active_variables: usize,
 	conflict_lens: Histo,
+	stats: Statistics,
+	failed_assumptions: Vec<Literal>,
+	next_clause_id: u64,
+	unit_ids: Vec<u64>,
+	proof: Option<Proof>,
+	limits: limits::Limits,
+	restarts: restart::Restarts,
+	original_clauses: Option<Vec<ClauseLiteralVec>>,
+	// `Unsat` once the problem is known to be unsatisfiable, and `Sat` as long as the current assignment is a model
 	solution: SolverResult,
 }
 
 impl<T: fmt::Display> Problem<T> {
 	pub fn model(&self) -> Vec<(&T, bool)> {
-		let mut result = Vec::with_capacity(self.variables.len().try_into().unwrap());
-		for (var, name) in self.variables.iter().zip(self.variable_names.iter()) {
-			debug_assert!(var.has_value());
-			result.push((name, var.get_value()));
+		let mut result = Vec::with_capacity(self.variable_names.len());
+		for (id, name) in self.variable_names.iter().enumerate() {
+			result.push((name, self.assignment.get_value(VariableId::from_usize(id))));
 		}
 		result
 	}
+
+	pub fn stats(&self) -> &Statistics {
+		&self.stats
+	}
+
+	/// Checks the current model against the original clauses, which have to be kept by means of
+	/// `ProblemBuilder::keep_original_clauses`. This only succeeds directly after the problem was found to be
+	/// satisfiable.
+	pub fn verify_model(&self) -> Result<(), crate::cnf::errors::Error> {
+		let original = self
+			.original_clauses
+			.as_ref()
+			.ok_or(crate::cnf::errors::Error::NoOriginalClauses)?;
+		if self.solution != SolverResult::Sat {
+			return Err(crate::cnf::errors::Error::NoModel);
+		}
+		for (index, clause) in original.iter().enumerate() {
+			if !clause
+				.iter()
+				.any(|&lit| self.assignment.literal_value(lit) == Some(true))
+			{
+				return Err(crate::cnf::errors::Error::FalsifiedClause { number: index + 1 });
+			}
+		}
+		Ok(())
+	}
+
+	/// Finishes writing the proof, if any, and reports the first error that occurred while doing so
+	pub fn finish_proof(&mut self) -> io::Result<()> {
+		match self.proof.take() {
+			Some(proof) => proof.finish(),
+			None => Ok(()),
+		}
+	}
+}
+
+// drops the removed clauses, keeping the order of the others
+fn retain(clauses: &mut Vec<ClauseLiteralVec>, ids: &mut Vec<u64>, removed: &[bool]) {
+	let old = std::mem::take(clauses)
+		.into_iter()
+		.zip(std::mem::take(ids))
+		.zip(removed);
+	for ((clause, id), &removed) in old {
+		if !removed {
+			clauses.push(clause);
+			ids.push(id);
+		}
+	}
 }
@@ -1,89 +1,161 @@
+use super::eliminate::Reconstruction;
 use crate::SolverResult;
-use crate::cnf::{ClauseLiteralVec, Variable, VariableId};
+use crate::cnf::{Assignment, ClauseLiteralVec, Literal, Proof, VariableId};
+use crate::util::Marks;
 
-pub fn precompute(variables: &mut Vec<Variable>, clauses: &mut Vec<ClauseLiteralVec>) -> SolverResult {
-	// sorting
+// asymmetric tautology elimination stops propagating through clauses with more than two literals after visiting this
+// many literals, and hidden tautology elimination stops altogether after this many
+const MAX_ASYMMETRIC_STEPS: usize = 1_000_000;
+const MAX_HIDDEN_STEPS: usize = 5_000_000;
+// blocked clause elimination gives up after visiting this many literals
+const MAX_BLOCKED_STEPS: usize = 3_000_000;
+// literals whose negation occurs more often are not tried as the blocking literal, as every clause that contains the
+// literal is resolved with all of these occurrences
+const MAX_BLOCKED_OCCURRENCES: usize = 100;
+
+// the state of an input clause during the preprocessing
+#[derive(Debug, Clone, Copy)]
+enum State {
+	// the number of literals that were not visited as false yet
+	Open(usize),
+	// the clause is the unit clause of its only literal, which the proof keeps referring to
+	Unit,
+	Satisfied,
+}
+
+// `ids` holds the proof id of each clause and is kept parallel to `clauses`, while `unit_ids` receives the id of the
+// unit clause that fixed each assigned variable
+pub fn precompute(
+	assignment: &mut Assignment,
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	unit_ids: &mut [u64],
+	next_id: &mut u64,
+	proof: &mut Option<Proof>,
+) -> SolverResult {
+	debug_assert_eq!(clauses.len(), ids.len());
+	let mut states = Vec::with_capacity(clauses.len());
 	for clause in clauses.iter_mut() {
 		clause.sort();
 		clause.dedup();
-		// TODO: dedup in conjunction with checking for clauses that contain both x and ~x
+		debug_assert!(!clause.is_empty());
+		// x and ¬x are next to each other after sorting
+		let tautology = clause.windows(2).any(|pair| pair[0].id() == pair[1].id());
+		states.push(if tautology {
+			State::Satisfied
+		} else {
+			State::Open(clause.len())
+		});
 	}
 
-	// unary propagation
-	{
-		let mut v = Vec::new();
-		let mut w = Vec::new();
-		loop {
-			let mut ci = 0;
-			while ci < clauses.len() {
-				let mut i = 0;
-				let mut k = 0;
-				let mut sat = false;
-				{
-					let clause = &mut clauses[ci];
-					let mut j = 0;
-					debug_assert!(!clause.is_empty());
-					while i < clause.len() && j < v.len() {
-						if clause[i].id() < v[j] {
-							if i != k {
-								clause[k] = clause[i];
-							}
-							i += 1;
-							k += 1;
-						} else if clause[i].id() > v[j] {
-							j += 1;
-						} else {
-							let var = &variables[v[j].to_usize()];
-							debug_assert!(var.has_value());
-							if clause[i].negated() != var.get_value() {
-								sat = true;
-								break;
-							}
-							i += 1;
-						}
-					}
-					if !sat && i < clause.len() {
-						if i != k {
-							while i < clause.len() {
-								clause[k] = clause[i];
-								i += 1;
-								k += 1;
-							}
-						} else {
-							i = clause.len();
-							k = clause.len();
-						}
-					}
+	// occurrence lists, indexed by literal
+	let mut occurrences = vec![Vec::new(); 2 * assignment.len()];
+	for (ci, clause) in clauses.iter().enumerate() {
+		if let State::Open(_) = states[ci] {
+			for lit in clause.iter() {
+				occurrences[lit.index()].push(ci);
+			}
+		}
+	}
+
+	// unary propagation, where `queue` holds the literals that were set to true, and whose occurrences are visited in
+	// the same order
+	let depth = VariableId::from_usize(0);
+	let mut queue = Vec::new();
+	for ci in 0..clauses.len() {
+		if let State::Open(1) = states[ci] {
+			let lit = clauses[ci][0];
+			match assignment.literal_value(lit) {
+				None => {
+					assignment.set(lit.id(), !lit.negated(), depth, None);
+					unit_ids[lit.id().to_usize()] = ids[ci];
+					queue.push(lit);
+					states[ci] = State::Unit;
 				}
-				if sat {
-					clauses.swap_remove(ci);
-				} else if k == 0 {
-					return SolverResult::Unsat;
-				} else if k == 1 {
-					let lit = clauses[ci][0];
-					let var = &mut variables[lit.id().to_usize()];
-					if var.has_value() {
-						if lit.negated() == var.get_value() {
-							return SolverResult::Unsat;
-						}
-					} else {
-						var.set(!lit.negated(), VariableId::from_usize(0), usize::MAX);
-						w.push(lit.id());
+				Some(true) => states[ci] = State::Satisfied,
+				Some(false) => {
+					if let Some(proof) = proof.as_mut() {
+						proof.add(*next_id, [], [unit_ids[lit.id().to_usize()], ids[ci]]);
 					}
-					clauses.swap_remove(ci);
-				} else {
-					if i != k {
-						clauses[ci].truncate(k);
-					}
-					ci += 1;
+					return SolverResult::Unsat;
 				}
 			}
-			if w.is_empty() {
-				break;
+		}
+	}
+	let mut head = 0;
+	while head < queue.len() {
+		let lit = queue[head];
+		head += 1;
+		for &ci in occurrences[lit.index()].iter() {
+			if let State::Open(_) = states[ci] {
+				states[ci] = State::Satisfied;
+			}
+		}
+		for &ci in occurrences[lit.negate().index()].iter() {
+			let State::Open(count) = states[ci] else {
+				continue;
+			};
+			if count > 2 {
+				states[ci] = State::Open(count - 1);
+				continue;
+			}
+			// at most one literal is unassigned now, while others may already be assigned without having been visited
+			debug_assert_eq!(count, 2);
+			let clause = &clauses[ci];
+			if clause
+				.iter()
+				.any(|&other| assignment.literal_value(other) == Some(true))
+			{
+				states[ci] = State::Satisfied;
+				continue;
+			}
+			let Some(&unit) = clause.iter().find(|&&other| assignment.literal_value(other).is_none()) else {
+				if let Some(proof) = proof.as_mut() {
+					proof.add(*next_id, [], removal_hints(clause, &[], ids[ci], unit_ids));
+				}
+				return SolverResult::Unsat;
+			};
+			let mut id = ids[ci];
+			if let Some(proof) = proof.as_mut() {
+				id = *next_id;
+				*next_id += 1;
+				proof.add(id, [unit], removal_hints(clause, &[unit], ids[ci], unit_ids));
+			}
+			assignment.set(unit.id(), !unit.negated(), depth, None);
+			unit_ids[unit.id().to_usize()] = id;
+			queue.push(unit);
+			// the unit clause takes the place of the clause, which is deleted below
+			states[ci] = State::Satisfied;
+		}
+	}
+
+	// drops the satisfied clauses and the false literals of the remaining ones, keeping the order of the clauses
+	let old = std::mem::take(clauses).into_iter().zip(std::mem::take(ids)).zip(states);
+	for ((mut clause, mut id), state) in old {
+		match state {
+			State::Unit => {}
+			State::Satisfied => {
+				if let Some(proof) = proof.as_mut() {
+					proof.delete(id, clause);
+				}
+			}
+			State::Open(_) => {
+				debug_assert!(clause.iter().all(|&lit| assignment.literal_value(lit) != Some(true)));
+				if clause.iter().any(|lit| assignment.has_value(lit.id())) {
+					let original = proof.as_ref().map(|_| clause.clone());
+					clause.retain(|lit| !assignment.has_value(lit.id()));
+					debug_assert!(clause.len() >= 2);
+					if let (Some(proof), Some(original)) = (proof.as_mut(), original) {
+						let hints = removal_hints(&original, &clause, id, unit_ids);
+						proof.add(*next_id, clause.iter().copied(), hints);
+						proof.delete(id, original);
+						id = *next_id;
+						*next_id += 1;
+					}
+				}
+				clauses.push(clause);
+				ids.push(id);
 			}
-			std::mem::swap(&mut v, &mut w);
-			v.sort();
-			w.clear();
 		}
 	}
 	if clauses.is_empty() {
@@ -92,3 +164,188 @@ pub fn precompute(variables: &mut Vec<Variable>, clauses: &mut Vec<ClauseLiteral
 		SolverResult::Unknown
 	}
 }
+
+// the unit clauses of the literals that were removed from `original` to obtain `remaining`, followed by `original`
+fn removal_hints(original: &[Literal], remaining: &[Literal], original_id: u64, unit_ids: &[u64]) -> Vec<u64> {
+	original
+		.iter()
+		.filter(|lit| !remaining.contains(lit))
+		.map(|lit| unit_ids[lit.id().to_usize()])
+		.chain(std::iter::once(original_id))
+		.collect()
+}
+
+// Hidden and asymmetric tautology elimination: a clause is removed if it is implied by the other clauses, which is the
+// case when unit propagation of its negated literals leads to a conflict. Propagation through binary clauses finds the
+// hidden tautologies, and propagation through longer clauses the asymmetric ones, until its budget is used up. Removed
+// clauses are implied by the remaining ones, so neither the proof nor the reconstruction needs more than their
+// deletion. Clauses are kept parallel to `ids` like in `precompute`. Returns the number of removed clauses.
+pub fn eliminate_tautologies(
+	variables: usize,
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	proof: &mut Option<Proof>,
+) -> u64 {
+	debug_assert_eq!(clauses.len(), ids.len());
+	// the occurrences in binary clauses are kept apart, so that hidden tautology elimination does not have to skip the
+	// other ones
+	let mut binaries = vec![Vec::new(); 2 * variables];
+	let mut occurrences = vec![Vec::new(); 2 * variables];
+	for (ci, clause) in clauses.iter().enumerate() {
+		let lists = if clause.len() == 2 {
+			&mut binaries
+		} else {
+			&mut occurrences
+		};
+		for lit in clause.iter() {
+			lists[lit.index()].push(ci);
+		}
+	}
+	let mut removed = vec![false; clauses.len()];
+	let mut count = 0;
+	let mut steps = 0;
+	// the literals that are true under the negation of the current clause, in the order of their assignment
+	let mut values = Marks::new();
+	let mut trail = Vec::new();
+	for ci in 0..clauses.len() {
+		if steps > MAX_HIDDEN_STEPS {
+			break;
+		}
+		values.clear();
+		trail.clear();
+		for &lit in clauses[ci].iter() {
+			values.insert(lit.negate().index());
+			trail.push(lit.negate());
+		}
+		let mut head = 0;
+		let mut conflict = false;
+		'propagation: while head < trail.len() {
+			let lit = trail[head];
+			head += 1;
+			let long = if steps > MAX_ASYMMETRIC_STEPS {
+				&[][..]
+			} else {
+				&occurrences[lit.negate().index()][..]
+			};
+			for &cj in binaries[lit.negate().index()].iter().chain(long) {
+				if cj == ci || removed[cj] {
+					continue;
+				}
+				let other = &clauses[cj];
+				steps += other.len();
+				if other.iter().any(|lit| values.contains(lit.index())) {
+					continue;
+				}
+				let mut open = other.iter().filter(|lit| !values.contains(lit.negate().index()));
+				match (open.next(), open.next()) {
+					(None, _) => {
+						conflict = true;
+						break 'propagation;
+					}
+					(Some(&unit), None) => {
+						values.insert(unit.index());
+						trail.push(unit);
+					}
+					_ => {}
+				}
+			}
+		}
+		if conflict {
+			removed[ci] = true;
+			count += 1;
+			if let Some(proof) = proof.as_mut() {
+				proof.delete(ids[ci], clauses[ci].iter().copied());
+			}
+		}
+	}
+	super::retain(clauses, ids, &removed);
+	count
+}
+
+// Blocked clause elimination: a clause is blocked on one of its literals if all resolvents on that literal are
+// tautologies, so that flipping the literal satisfies the clause without falsifying any other clause. Blocked clauses
+// are removed and pushed onto `reconstruction` with that literal as the witness, while the proof keeps them, as
+// incremental calls may restore them. Frozen variables are never witnesses. Clauses are kept parallel to `ids` like in
+// `precompute`. Returns the number of removed clauses.
+pub fn eliminate_blocked(
+	variables: usize,
+	frozen: &[VariableId],
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	reconstruction: &mut Reconstruction,
+) -> u64 {
+	debug_assert_eq!(clauses.len(), ids.len());
+	// occurrence lists, indexed by literal, which may still refer to removed clauses
+	let mut occurrences = occurrences(variables, clauses);
+	let mut removed = vec![false; clauses.len()];
+	let mut skipped = Marks::new();
+	for id in frozen {
+		skipped.insert(id.to_usize());
+	}
+	// the candidates for the witness, where a literal is queued again once a clause containing its negation is removed.
+	// The highest variables come first, as encodings tend to number the outputs of gates after their inputs.
+	let mut queue: Vec<Literal> = (0..2 * variables)
+		.map(Literal::from_raw)
+		.filter(|lit| !skipped.contains(lit.id().to_usize()))
+		.collect();
+	let mut queued = Marks::new();
+	for lit in queue.iter() {
+		queued.insert(lit.index());
+	}
+	let mut literals = Marks::new();
+	let mut count = 0;
+	let mut steps = 0;
+	while let Some(lit) = queue.pop() {
+		if steps > MAX_BLOCKED_STEPS {
+			break;
+		}
+		queued.remove(lit.index());
+		let negation_index = lit.negate().index();
+		occurrences[negation_index].retain(|&cj| !removed[cj]);
+		if occurrences[negation_index].len() > MAX_BLOCKED_OCCURRENCES {
+			continue;
+		}
+		for oi in 0..occurrences[lit.index()].len() {
+			let ci = occurrences[lit.index()][oi];
+			if removed[ci] {
+				continue;
+			}
+			literals.clear();
+			for other in clauses[ci].iter() {
+				literals.insert(other.index());
+			}
+			let blocked = occurrences[negation_index].iter().all(|&cj| {
+				steps += clauses[cj].len();
+				clauses[cj]
+					.iter()
+					.any(|&other| other != lit.negate() && literals.contains(other.negate().index()))
+			});
+			if !blocked {
+				continue;
+			}
+			removed[ci] = true;
+			count += 1;
+			let clause = std::mem::take(&mut clauses[ci]);
+			for &other in clause.iter() {
+				let candidate = other.negate();
+				if !skipped.contains(other.id().to_usize()) && queued.insert(candidate.index()) {
+					queue.push(candidate);
+				}
+			}
+			reconstruction.push(lit, clause, ids[ci]);
+		}
+	}
+	super::retain(clauses, ids, &removed);
+	count
+}
+
+// occurrence lists, indexed by literal
+fn occurrences(variables: usize, clauses: &[ClauseLiteralVec]) -> Vec<Vec<usize>> {
+	let mut occurrences = vec![Vec::new(); 2 * variables];
+	for (ci, clause) in clauses.iter().enumerate() {
+		for lit in clause.iter() {
+			occurrences[lit.index()].push(ci);
+		}
+	}
+	occurrences
+}
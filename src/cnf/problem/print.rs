@@ -1,29 +1,60 @@
 use std::{fmt, io, str};
 
 use super::Problem;
+use crate::cnf::{Literal, VariableId};
 
 impl<T: fmt::Display> Problem<T> {
 	pub fn print(&self, writer: &mut impl io::Write) -> io::Result<()> {
-		writeln!(writer, "Problem of {} clauses:", self.clauses.len())?;
-		for clause in &self.clauses {
-			clause.print(writer, &self.variable_names)?;
+		writeln!(
+			writer,
+			"Problem of {} clauses:",
+			self.clauses.len() + self.binary_clauses().count()
+		)?;
+		for cref in self.clauses.iter() {
+			self.clauses.print(cref, writer, &self.variable_names)?;
+			writeln!(writer)?;
+		}
+		for [a, b] in self.binary_clauses() {
+			a.print(writer, &self.variable_names[a.id().to_usize()])?;
+			write!(writer, " ")?;
+			b.print(writer, &self.variable_names[b.id().to_usize()])?;
 			writeln!(writer)?;
 		}
 		Ok(())
 	}
 
+	// every binary clause is contained in the implication lists of both of its literals, but only returned once
+	fn binary_clauses(&self) -> impl Iterator<Item = [Literal; 2]> + '_ {
+		self.variables.iter().enumerate().flat_map(|(id, var)| {
+			[false, true].into_iter().flat_map(move |negated| {
+				let lit = Literal::new(VariableId::from_usize(id), negated);
+				var
+					.get_implications(negated)
+					.iter()
+					.filter(move |implication| lit < implication.literal)
+					.map(move |implication| [lit, implication.literal])
+			})
+		})
+	}
+
 	pub fn print_model(&self, writer: &mut impl io::Write, indent: &str) -> io::Result<()> {
-		for (var, name) in self.variables.iter().zip(self.variable_names.iter()) {
+		for (id, name) in self.variable_names.iter().enumerate() {
 			// FIXME: allow using &self.variables here
-			debug_assert!(var.has_value());
-			writeln!(writer, "{}{}: {}", indent, name, var.get_value())?;
+			let value = self.assignment.get_value(VariableId::from_usize(id));
+			writeln!(writer, "{}{}: {}", indent, name, value)?;
 		}
 		Ok(())
 	}
 
 	pub fn print_clauses(&self, writer: &mut impl io::Write) -> io::Result<()> {
-		for clause in &self.clauses {
-			for lit in clause.iter() {
+		let binary = self.binary_clauses().collect::<Vec<_>>();
+		for literals in self
+			.clauses
+			.iter()
+			.map(|cref| self.clauses.literals(cref))
+			.chain(binary.iter().map(|literals| &literals[..]))
+		{
+			for lit in literals {
 				write!(
 					writer,
 					"{}{} ",
@@ -37,7 +68,7 @@ impl<T: fmt::Display> Problem<T> {
 	}
 
 	pub fn print_conflict_histo(&self, writer: &mut impl io::Write) -> io::Result<()> {
-		writeln!(writer, "{} conflicts: {}", self.num_conflicts, self.conflict_lens)?;
+		writeln!(writer, "{} conflicts: {}", self.stats.conflicts, self.conflict_lens)?;
 		let mut x = 0u64;
 		for i in 0..self.conflict_lens.bins.len() {
 			x += self.conflict_lens.bins[i] * ((i + 1) as u64);
@@ -46,9 +77,20 @@ impl<T: fmt::Display> Problem<T> {
 	}
 
 	pub fn print_dimacs(&self, writer: &mut impl io::Write) -> io::Result<()> {
-		writeln!(writer, "p cnf {} {}", self.active_variables, self.clauses.len())?;
-		for clause in self.clauses.iter() {
-			for lit in clause.iter() {
+		let binary = self.binary_clauses().collect::<Vec<_>>();
+		writeln!(
+			writer,
+			"p cnf {} {}",
+			self.active_variables,
+			self.clauses.len() + binary.len()
+		)?;
+		for literals in self
+			.clauses
+			.iter()
+			.map(|cref| self.clauses.literals(cref))
+			.chain(binary.iter().map(|literals| &literals[..]))
+		{
+			for lit in literals {
 				if lit.negated() {
 					write!(writer, "-")?;
 				}
@@ -0,0 +1,275 @@
+use std::fmt;
+
+use crate::cnf::{ClauseRef, Literal, Problem, VariableId};
+use crate::util::Marks;
+
+// probing may take up this fraction of all propagations, on top of a fixed amount that is available right away
+const PROBING_EFFORT: f64 = 0.05;
+const PROBING_PROPAGATIONS: u64 = 100_000;
+// during the search, probing happens every this many reductions of the learned clauses, together with their
+// subsumption, which restarts the search anyway
+pub(super) const PROBING_INTERVAL: u64 = super::reduce::SUBSUMPTION_INTERVAL;
+
+/// Where probing continues and how much work it has done, so that repeated rounds cover all variables
+#[derive(Debug, Default)]
+pub(super) struct Probing {
+	next: usize,
+	propagations: u64,
+	// the hyper-binary resolvents that are still in the implication lists, which are redundant like learned clauses
+	resolvents: Vec<([Literal; 2], u64)>,
+}
+
+// The literals implied by a probed literal, in trail order starting with the probed literal itself. Hyper-binary
+// resolution gives every implied literal a binary clause as its reason, whose other literal is its parent, so that the
+// path from a literal up to the root proves that the root implies it.
+struct Tree {
+	nodes: Vec<Node>,
+	// the position of each variable in `nodes`, which is only meaningful for the variables in the tree
+	positions: Vec<usize>,
+	visited: Marks,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+	lit: Literal,
+	parent: usize,
+	// the proof id of the binary clause of the literal and the negation of its parent, which is only looked up if a
+	// proof is written
+	id: u64,
+}
+
+impl Tree {
+	fn new(variables: usize) -> Tree {
+		Tree {
+			nodes: Vec::new(),
+			positions: vec![0; variables],
+			visited: Marks::new(),
+		}
+	}
+
+	fn push(&mut self, node: Node) {
+		self.positions[node.lit.id().to_usize()] = self.nodes.len();
+		self.nodes.push(node);
+	}
+
+	fn position(&self, lit: Literal) -> Option<usize> {
+		let position = self.positions[lit.id().to_usize()];
+		match self.nodes.get(position) {
+			Some(node) if node.lit == lit => Some(position),
+			_ => None,
+		}
+	}
+
+	// the deepest node that lies on the paths from all given nodes to the root
+	fn dominator(&self, positions: &[usize]) -> usize {
+		let mut dominator = positions[0];
+		for &start in positions[1..].iter() {
+			let mut position = start;
+			while position != dominator {
+				if position > dominator {
+					position = self.nodes[position].parent;
+				} else {
+					dominator = self.nodes[dominator].parent;
+				}
+			}
+		}
+		dominator
+	}
+
+	// the ids of the binary clauses on the paths from the given nodes up to their ancestor `to`, in trail order
+	fn path_hints(&mut self, from: &[usize], to: usize) -> Vec<u64> {
+		let mut path = Vec::new();
+		for &start in from {
+			let mut position = start;
+			while position != to && self.visited.insert(position) {
+				path.push(position);
+				position = self.nodes[position].parent;
+			}
+		}
+		self.visited.clear();
+		path.sort_unstable();
+		path.into_iter().map(|position| self.nodes[position].id).collect()
+	}
+}
+
+impl<T: fmt::Display> Problem<T> {
+	// Deletes the hyper-binary resolvents of earlier probing rounds, so that repeated rounds do not pile them up.
+	// Resolvents that are the reason of an assignment are kept until the next reduction.
+	pub(super) fn delete_resolvents(&mut self) {
+		let mut kept = Vec::new();
+		for (literals, id) in std::mem::take(&mut self.probing.resolvents) {
+			// reasons only keep the literals of binary clauses, so any copy of them counts
+			let [a, b] = literals;
+			let is_reason = [[a, b], [b, a]].into_iter().any(|[lit, other]| {
+				self.assignment.has_value(lit.id())
+					&& self.assignment.get_ante(lit.id()) == Some(ClauseRef::Binary([lit, other]))
+			});
+			if is_reason {
+				kept.push((literals, id));
+				continue;
+			}
+			for lit in literals {
+				self.variables[lit.id().to_usize()].remove_implication(lit.negated(), id);
+			}
+			if let Some(proof) = self.proof.as_mut() {
+				proof.delete(id, literals);
+			}
+		}
+		self.probing.resolvents = kept;
+	}
+
+	// Failed literal probing: each literal that implies others through binary clauses is assigned at depth 1 and
+	// propagated. If this fails, the learned unit clause is added at depth 0, and otherwise the literals that are
+	// implied by both values of the variable become units. Hyper-binary resolvents replace the long reasons of implied
+	// literals along the way. Probing restarts the search, continues where the last round stopped and returns as soon
+	// as its share of the propagations is used up. Returns the conflict if the problem turns out to be unsatisfiable.
+	pub(super) fn probe(&mut self) -> Option<ClauseRef> {
+		let budget = ((self.stats.propagations as f64 * PROBING_EFFORT) as u64 + PROBING_PROPAGATIONS)
+			.saturating_sub(self.probing.propagations);
+		if budget == 0 {
+			return None;
+		}
+		self.restart();
+		let start = self.stats.propagations;
+		let varcount = self.variables.len();
+		let mut trees = [Tree::new(varcount), Tree::new(varcount)];
+		for _ in 0..varcount {
+			if self.stats.propagations - start >= budget {
+				break;
+			}
+			let id = VariableId::from_usize(self.probing.next % varcount);
+			self.probing.next = (self.probing.next + 1) % varcount;
+			for (tree, value) in trees.iter_mut().zip([true, false]) {
+				tree.nodes.clear();
+				if self.assignment.has_value(id) || self.variables[id.to_usize()].get_implications(value).is_empty() {
+					continue;
+				}
+				if let Err(conflict) = self.probe_literal(Literal::new(id, !value), tree) {
+					self.stats.failed_literals += 1;
+					let (lits, hints) = self.learn(conflict);
+					debug_assert_eq!(lits.len(), 1);
+					let unit_id = self.new_clause_id();
+					if let Some(proof) = self.proof.as_mut() {
+						proof.add(unit_id, lits.iter().copied(), hints);
+					}
+					self.restart();
+					if let Some(conflict) = self.propagate_unit(lits[0], unit_id) {
+						return Some(conflict);
+					}
+				}
+				self.restart();
+			}
+			if let Some(conflict) = self.learn_implied(&mut trees) {
+				return Some(conflict);
+			}
+		}
+		self.probing.propagations += self.stats.propagations - start;
+		None
+	}
+
+	// assigns `lit` at depth 1, propagates it, and fills `tree` with its implications, unless it leads to a conflict
+	fn probe_literal(&mut self, lit: Literal, tree: &mut Tree) -> Result<(), ClauseRef> {
+		debug_assert!(self.depth.to_usize() == 0 && self.applications.is_empty());
+		self.depth = VariableId::from_usize(1);
+		self.assignment.set(lit.id(), !lit.negated(), self.depth, None);
+		self.applications.push(lit.id());
+		self.heuristic.assigned(lit.id());
+		if let Some(conflict) = self.propagate() {
+			return Err(conflict);
+		}
+		tree.push(Node { lit, parent: 0, id: 0 });
+		for ai in 1..self.applications.len() {
+			let vid = self.applications[ai];
+			let implied = Literal::new(vid, !self.assignment.get_value(vid));
+			let node = match self.assignment.get_ante(vid).expect("the assignment was implied") {
+				ante @ ClauseRef::Binary([_, falsified]) => Node {
+					lit: implied,
+					parent: tree.positions[falsified.id().to_usize()],
+					id: match self.proof {
+						Some(_) => ante.id(&self.clauses, &self.variables),
+						None => 0,
+					},
+				},
+				ClauseRef::Long(cref) => {
+					// the falsified literals of depth 0 are left out of the resolvent
+					let literals = self.clauses.literals(cref);
+					let parents: Vec<usize> = literals
+						.iter()
+						.filter(|lit| lit.id() != vid && self.assignment.get_depth(lit.id()).to_usize() != 0)
+						.map(|lit| tree.positions[lit.id().to_usize()])
+						.collect();
+					let parent = tree.dominator(&parents);
+					let mut hints: Vec<u64> = literals
+						.iter()
+						.filter(|lit| self.assignment.get_depth(lit.id()).to_usize() == 0)
+						.map(|lit| self.unit_ids[lit.id().to_usize()])
+						.collect();
+					hints.extend(tree.path_hints(&parents, parent));
+					hints.push(self.clauses.id(cref));
+					let negation = tree.nodes[parent].lit.negate();
+					let id = self.new_clause_id();
+					if let Some(proof) = self.proof.as_mut() {
+						proof.add(id, [negation, implied], hints);
+					}
+					self.add_binary_clause([negation, implied], id);
+					self.probing.resolvents.push(([negation, implied], id));
+					self
+						.assignment
+						.set_ante(vid, Some(ClauseRef::Binary([implied, negation])));
+					self.stats.hyper_binary_resolvents += 1;
+					Node {
+						lit: implied,
+						parent,
+						id,
+					}
+				}
+			};
+			tree.push(node);
+		}
+		Ok(())
+	}
+
+	// adds the literals that are implied by both roots of `trees` as unit clauses, each of which is proven by the
+	// binary clauses of the implications, which are deleted right away
+	fn learn_implied(&mut self, trees: &mut [Tree; 2]) -> Option<ClauseRef> {
+		let [positive, negative] = trees;
+		if positive.nodes.is_empty() || negative.nodes.is_empty() {
+			return None;
+		}
+		for ni in 1..negative.nodes.len() {
+			let lit = negative.nodes[ni].lit;
+			let Some(position) = positive.position(lit) else {
+				continue;
+			};
+			// units that were found before may have assigned it already
+			if self.assignment.has_value(lit.id()) {
+				continue;
+			}
+			self.stats.implied_units += 1;
+			let unit_id = self.new_clause_id();
+			if self.proof.is_some() {
+				let mut ids = Vec::new();
+				for (tree, position) in [(&mut *positive, position), (&mut *negative, ni)] {
+					let root = tree.nodes[0].lit;
+					let clause = [root.negate(), lit];
+					let id = self.new_clause_id();
+					let hints = tree.path_hints(&[position], 0);
+					if let Some(proof) = self.proof.as_mut() {
+						proof.add(id, clause, hints);
+					}
+					ids.push((id, clause));
+				}
+				if let Some(proof) = self.proof.as_mut() {
+					proof.add(unit_id, [lit], ids.iter().map(|&(id, _)| id));
+					for (id, clause) in ids {
+						proof.delete(id, clause);
+					}
+				}
+			}
+			if let Some(conflict) = self.propagate_unit(lit, unit_id) {
+				return Some(conflict);
+			}
+		}
+		None
+	}
+}
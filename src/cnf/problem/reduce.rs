@@ -0,0 +1,175 @@
+use std::fmt;
+
+use crate::cnf::{ArenaRef, ClauseRef, Problem};
+
+// the activities of learned clauses are scaled down before they can overflow
+const RESCALE_LIMIT: f32 = 1e20;
+
+// the clause arena is compacted once more than this fraction of it is occupied by deleted clauses
+const MAX_FRAGMENTATION: f64 = 0.5;
+
+// learned clauses are checked for subsumption every this many reductions
+pub(super) const SUBSUMPTION_INTERVAL: u64 = 3;
+
+/// The learned clauses that may still be deleted. Clauses in tier 2 are kept for as long as they keep being used in
+/// conflict analysis, while the local tier is aged out by activity. Clauses of the core tier are never deleted, so
+/// they are not tracked at all.
+#[derive(Debug)]
+pub(super) struct Tiers {
+	tier2: Vec<ArenaRef>,
+	local: Vec<ArenaRef>,
+	increment: f32,
+}
+
+impl Tiers {
+	pub(super) fn new() -> Tiers {
+		Tiers {
+			tier2: Vec::new(),
+			local: Vec::new(),
+			increment: 1.0,
+		}
+	}
+}
+
+impl<T: fmt::Display> Problem<T> {
+	pub(super) fn add_learned(&mut self, cref: ArenaRef) {
+		let glue = self.clauses.get_glue(cref).to_usize();
+		if glue <= self.config.core_glue as usize {
+			self.stats.core_clauses += 1;
+		} else if glue <= self.config.tier2_glue as usize {
+			self.tiers.tier2.push(cref);
+		} else {
+			self.tiers.local.push(cref);
+		}
+		self.update_tier_stats();
+	}
+
+	fn update_tier_stats(&mut self) {
+		self.stats.tier2_clauses = self.tiers.tier2.len() as u64;
+		self.stats.local_clauses = self.tiers.local.len() as u64;
+	}
+
+	// marks a clause that takes part in conflict analysis as used and increases its activity
+	pub(super) fn bump_clause(&mut self, cref: ArenaRef) {
+		if !self.clauses.is_learned(cref) {
+			return;
+		}
+		self.clauses.mark_used(cref);
+		let activity = self.clauses.get_activity(cref) + self.tiers.increment;
+		self.clauses.set_activity(cref, activity);
+		if activity > RESCALE_LIMIT {
+			self.clauses.scale_activities(1.0 / RESCALE_LIMIT);
+			self.tiers.increment /= RESCALE_LIMIT;
+		}
+	}
+
+	pub(super) fn decay_clause_activities(&mut self) {
+		self.tiers.increment /= self.config.clause_decay as f32;
+	}
+
+	// moves the learned clauses between the tiers, and deletes the least active clauses of the local tier, except for
+	// those that are reasons of current assignments. Every few reductions, subsumed learned clauses are removed as well,
+	// which restarts the search and returns the conflict if the problem turns out to be unsatisfiable.
+	pub(super) fn delete_clauses(&mut self) -> Option<ClauseRef> {
+		self.stats.reductions += 1;
+		let core_glue = self.config.core_glue as usize;
+		let tier2_glue = self.config.tier2_glue as usize;
+		let clauses = &mut self.clauses;
+		let stats = &mut self.stats;
+		// the glue of a clause only ever decreases, which promotes it, while unused clauses of tier 2 are demoted
+		let mut demoted = Vec::new();
+		self.tiers.tier2.retain(|&cref| {
+			if clauses.get_glue(cref).to_usize() <= core_glue {
+				stats.core_clauses += 1;
+				false
+			} else if clauses.take_used(cref) {
+				true
+			} else {
+				demoted.push(cref);
+				false
+			}
+		});
+		let tier2 = &mut self.tiers.tier2;
+		self.tiers.local.retain(|&cref| {
+			let glue = clauses.get_glue(cref).to_usize();
+			if glue <= core_glue {
+				stats.core_clauses += 1;
+				false
+			} else if glue <= tier2_glue {
+				// promoted clauses get one round to prove their worth
+				clauses.mark_used(cref);
+				tier2.push(cref);
+				false
+			} else {
+				true
+			}
+		});
+		self.tiers.local.extend(demoted);
+		self.delete_resolvents();
+
+		let clauses = &self.clauses;
+		self
+			.tiers
+			.local
+			.sort_by(|&a, &b| clauses.get_activity(a).total_cmp(&clauses.get_activity(b)));
+		let mut deletions = (self.tiers.local.len() as f64 * self.config.reduce_fraction) as usize;
+		for &cref in self.tiers.local.iter() {
+			if deletions == 0 {
+				break;
+			}
+			if self.clauses.is_reason(cref, &self.assignment) {
+				continue;
+			}
+			deletions -= 1;
+			self.stats.deleted_clauses += 1;
+			if let Some(proof) = self.proof.as_mut() {
+				proof.delete(self.clauses.id(cref), self.clauses.literals(cref).iter().copied());
+			}
+			self.clauses.delete(cref);
+		}
+		let conflict = if self.stats.reductions % SUBSUMPTION_INTERVAL == 0 {
+			self.subsume_learned()
+		} else {
+			None
+		};
+		let clauses = &self.clauses;
+		self.tiers.tier2.retain(|&cref| !clauses.is_deleted(cref));
+		self.tiers.local.retain(|&cref| !clauses.is_deleted(cref));
+		self.update_tier_stats();
+
+		if self.clauses.fragmentation() > MAX_FRAGMENTATION {
+			self.collect_garbage();
+		}
+		conflict
+	}
+
+	// compacts the clause arena and updates all references to the moved clauses
+	fn collect_garbage(&mut self) {
+		let clauses = &self.clauses;
+		for var in self.variables.iter_mut() {
+			for negated in [false, true] {
+				var
+					.get_watchers(negated)
+					.retain(|watcher| !clauses.is_deleted(watcher.cid));
+			}
+		}
+		let relocations = self.clauses.compact();
+		for var in self.variables.iter_mut() {
+			for negated in [false, true] {
+				for watcher in var.get_watchers(negated).iter_mut() {
+					watcher.cid = relocations.get(watcher.cid);
+				}
+			}
+		}
+		for &id in self.applications.iter() {
+			if let Some(ClauseRef::Long(cref)) = self.assignment.get_ante(id) {
+				self
+					.assignment
+					.set_ante(id, Some(ClauseRef::Long(relocations.get(cref))));
+			}
+		}
+		for cref in self.tiers.tier2.iter_mut().chain(self.tiers.local.iter_mut()) {
+			*cref = relocations.get(*cref);
+		}
+	}
+}
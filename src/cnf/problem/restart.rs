@@ -0,0 +1,114 @@
+use std::fmt;
+
+use crate::cnf::config::RestartPolicy;
+use crate::cnf::{Problem, SolverConfig};
+
+// the smoothing factors of the short and long term moving averages of the glue of learned clauses
+const FAST_SMOOTHING: f64 = 1.0 / 32.0;
+const SLOW_SMOOTHING: f64 = 1.0 / 4096.0;
+
+/// An exponential moving average that is not biased towards zero while it has seen only few values
+#[derive(Debug, Clone)]
+struct Ema {
+	value: f64,
+	smoothing: f64,
+	count: u64,
+}
+
+impl Ema {
+	fn new(smoothing: f64) -> Ema {
+		Ema {
+			value: 0.0,
+			smoothing,
+			count: 0,
+		}
+	}
+
+	fn add(&mut self, value: f64) {
+		self.count += 1;
+		let rate = self.smoothing.max(1.0 / self.count as f64);
+		self.value += rate * (value - self.value);
+	}
+}
+
+/// Decides when the search is restarted, which happens independently of the deletion of learned clauses
+#[derive(Debug, Clone)]
+pub(super) struct Restarts {
+	conflicts: u64,
+	limit: f64,
+	count: u64,
+	fast_glue: Ema,
+	slow_glue: Ema,
+}
+
+impl Restarts {
+	pub(super) fn new(config: &SolverConfig) -> Restarts {
+		Restarts {
+			conflicts: 0,
+			limit: config.restart_interval as f64,
+			count: 0,
+			fast_glue: Ema::new(FAST_SMOOTHING),
+			slow_glue: Ema::new(SLOW_SMOOTHING),
+		}
+	}
+}
+
+// the Luby sequence 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, ... starting at index 0
+fn luby(mut index: u64) -> u64 {
+	let mut size = 1;
+	let mut exponent = 0;
+	while size < index + 1 {
+		exponent += 1;
+		size = 2 * size + 1;
+	}
+	while size - 1 != index {
+		size = (size - 1) >> 1;
+		exponent -= 1;
+		index %= size;
+	}
+	1 << exponent
+}
+
+impl<T: fmt::Display> Problem<T> {
+	pub(super) fn update_restarts(&mut self, glue: usize) {
+		self.restarts.conflicts += 1;
+		self.restarts.fast_glue.add(glue as f64);
+		self.restarts.slow_glue.add(glue as f64);
+	}
+
+	pub(super) fn should_restart(&self) -> bool {
+		let restarts = &self.restarts;
+		match self.config.restart_policy {
+			RestartPolicy::Luby | RestartPolicy::Geometric => restarts.conflicts as f64 >= restarts.limit,
+			RestartPolicy::Glucose => {
+				restarts.conflicts >= self.config.restart_interval as u64
+					&& restarts.fast_glue.value > self.config.restart_margin * restarts.slow_glue.value
+			}
+		}
+	}
+
+	// restarts the search and schedules the next restart
+	pub(super) fn scheduled_restart(&mut self) {
+		self.restart();
+		self.stats.restarts += 1;
+		let restarts = &mut self.restarts;
+		restarts.conflicts = 0;
+		restarts.count += 1;
+		match self.config.restart_policy {
+			RestartPolicy::Luby => {
+				restarts.limit = (self.config.restart_interval as u64).saturating_mul(luby(restarts.count)) as f64;
+			}
+			RestartPolicy::Geometric => restarts.limit *= self.config.restart_factor,
+			RestartPolicy::Glucose => {}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn luby() {
+		let sequence: Vec<u64> = (0..15).map(super::luby).collect();
+		assert_eq!(sequence, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+	}
+}
@@ -1,39 +1,111 @@
 use std::fmt;
 
 use crate::SolverResult;
-use crate::cnf::clause::Apply;
-use crate::cnf::{Clause, ClauseLiteralVec, Literal, Problem, VariableId};
+use crate::cnf::clause::{Apply, prepare_learned};
+use crate::cnf::{ClauseLiteralVec, ClauseRef, Implication, Literal, Problem, VariableId};
+use crate::util::{Marks, Stopwatch};
 
 impl<T: fmt::Display> Problem<T> {
 	pub fn solve(&mut self) -> SolverResult {
-		if self.solution != SolverResult::Unknown {
-			return self.solution;
+		self.solve_with_assumptions(&[])
+	}
+
+	/// Solves the problem under the given assumptions, which are treated as pseudo-decisions at the bottom of the trail.
+	///
+	/// Learned clauses are kept between calls, so that closely related queries can be answered incrementally. If the
+	/// result is `Unsat` only due to (some of) the assumptions, the involved assumptions are available from
+	/// `failed_assumptions` afterwards. The result is `Unknown` if one of the limits was hit before the query was
+	/// decided, in which case solving can be resumed by calling this function again.
+	pub fn solve_with_assumptions(&mut self, assumptions: &[Literal]) -> SolverResult {
+		self.failed_assumptions.clear();
+		self.restart();
+		for lit in assumptions {
+			self.reactivate(lit.id());
+		}
+		if self.solution == SolverResult::Unsat {
+			return SolverResult::Unsat;
 		}
-		let mut gc_next: u32 = 2047; // a u32 is safe, as the runtime to cause an overflow is prohibitive
+		let mut stopwatch = Stopwatch::new();
+		let result = self.search(assumptions);
+		stopwatch.stop();
+		self.stats.search_time += stopwatch.elapsed();
+		result
+	}
+
+	fn search(&mut self, assumptions: &[Literal]) -> SolverResult {
+		self.restart();
+		let mut stopwatch = Stopwatch::new();
+		let mut budget = self.budget();
+		let mut gc_next: u32 = self.config.reduce_interval; // a u32 is safe, as the runtime to cause an overflow is prohibitive
 		let mut gc_pos: u32 = 0;
-		let mut conflict: Option<usize> = None;
+		let mut conflict: Option<ClauseRef> = None;
 		loop {
-			self.update_q(&conflict);
-			if let Some(cid) = conflict {
+			self.heuristic.propagated(conflict.is_some());
+			if let Some(cref) = conflict {
 				if self.depth.to_usize() == 0 {
+					self.log_empty_clause(cref);
+					self.solution = SolverResult::Unsat;
 					return SolverResult::Unsat;
 				}
-				if self.alpha > 0.06 {
-					self.alpha -= 1e-6;
-				}
 				gc_pos += 1;
-				self.num_conflicts += 1;
-				let lits = self.learn(cid);
-				conflict = self.propagate_learned(lits);
+				self.stats.conflicts += 1;
+				stopwatch.start();
+				let (lits, hints) = self.learn(cref);
+				self.heuristic.learned();
+				self.decay_clause_activities();
+				stopwatch.stop();
+				self.stats.analysis_time += stopwatch.elapsed();
+				conflict = self.propagate_learned(lits, hints);
 			} else {
-				if self.active_variables == self.applications.len() {
-					return SolverResult::Sat;
+				if self.should_restart() {
+					self.scheduled_restart();
 				}
+
 				if gc_pos >= gc_next {
-					gc_next += 512;
+					gc_next = gc_next.saturating_add(self.config.reduce_increment);
 					gc_pos = 0;
-					self.restart(); // FIXME: restarts and garbage collection should be independent!
-					self.delete_clauses();
+					stopwatch.start();
+					conflict = self.delete_clauses();
+					stopwatch.stop();
+					self.stats.reduction_time += stopwatch.elapsed();
+					if conflict.is_none() && self.config.probe && self.stats.reductions % super::probe::PROBING_INTERVAL == 0 {
+						stopwatch.start();
+						conflict = self.probe();
+						stopwatch.stop();
+						self.stats.probing_time += stopwatch.elapsed();
+					}
+					if conflict.is_some() {
+						continue;
+					}
+				}
+
+				if self.is_exhausted(&mut budget) {
+					return SolverResult::Unknown;
+				}
+
+				if let Some(&lit) = assumptions.get(self.depth.to_usize()) {
+					match self.assignment.value(lit.id()) {
+						Some(value) if value != lit.negated() => {
+							// already satisfied, so open an empty decision level to keep depths and assumptions aligned
+							self.depth = VariableId::from_usize(self.depth.to_usize() + 1);
+						}
+						Some(_) => {
+							self.analyze_final(lit);
+							self.restart();
+							return SolverResult::Unsat;
+						}
+						None => {
+							self.decide(lit.id(), !lit.negated());
+							conflict = self.propagate();
+						}
+					}
+					continue;
+				}
+
+				if self.active_variables == self.applications.len() {
+					self.reconstruction.extend(&mut self.assignment);
+					self.solution = SolverResult::Sat;
+					return SolverResult::Sat;
 				}
 
 				self.choose();
@@ -42,45 +114,94 @@ impl<T: fmt::Display> Problem<T> {
 		}
 	}
 
-	fn learn(&mut self, mut cid: usize) -> ClauseLiteralVec {
-		debug_assert!(self.depth.to_usize() > 0);
-		for lit in self.clauses[cid].iter() {
-			self.last_conflict[lit.id().to_usize()] = self.num_conflicts;
-			debug_assert!(self.variables[lit.id().to_usize()].has_value());
+	/// The subset of assumptions of the last call to `solve_with_assumptions` that was found to be unsatisfiable.
+	pub fn failed_assumptions(&self) -> &[Literal] {
+		&self.failed_assumptions
+	}
+
+	// collects the assumptions that imply the negation of the falsified assumption `lit`
+	fn analyze_final(&mut self, lit: Literal) {
+		self.failed_assumptions.push(lit);
+		if self.assignment.get_depth(lit.id()).to_usize() == 0 {
+			return;
 		}
+		self.marks.insert(lit.id().to_usize());
+		for &id in self.applications.iter().rev() {
+			if !self.marks.contains(id.to_usize()) {
+				continue;
+			}
+			match self.assignment.get_ante(id) {
+				None => {
+					// all decisions on the trail are assumptions at this point
+					self
+						.failed_assumptions
+						.push(Literal::new(id, !self.assignment.get_value(id)));
+				}
+				Some(ante) => {
+					for other in ante.literals(&self.clauses).iter().map(|lit| lit.id()) {
+						if self.assignment.get_depth(other).to_usize() != 0 {
+							self.marks.insert(other.to_usize());
+						}
+					}
+				}
+			}
+		}
+		self.marks.clear();
+	}
+
+	// returns the learned clause together with its LRAT hints, which are only computed if they are needed
+	pub(super) fn learn(&mut self, conflict: ClauseRef) -> (ClauseLiteralVec, Vec<u64>) {
+		let mut cref = conflict;
+		debug_assert!(self.depth.to_usize() > 0);
+		debug_assert!(
+			cref
+				.literals(&self.clauses)
+				.iter()
+				.all(|lit| self.assignment.has_value(lit.id()))
+		);
 		debug_assert!(
-			self.clauses[cid]
+			cref
+				.literals(&self.clauses)
 				.iter()
-				.map(|lit| self.variables[lit.id().to_usize()].get_depth())
+				.map(|lit| self.assignment.get_depth(lit.id()))
 				.max()
 				.unwrap()
 				== self.depth
 		);
-		let mut marks = vec![false; self.variables.len()];
+		for lit in cref.literals(&self.clauses).iter() {
+			self.heuristic.conflicting(lit.id());
+		}
+		let mut marks = std::mem::take(&mut self.marks);
+		let mut queue = std::mem::take(&mut self.analysis_queue);
 		let mut lits = ClauseLiteralVec::new();
-		let mut queue = Vec::<usize>::with_capacity(self.clauses[cid].len());
 		let mut implicated = VariableId::MAX;
 		loop {
-			for (id, negated) in self.clauses[cid].iter().map(|lit| lit.disassemble()) {
-				debug_assert!(self.variables[id.to_usize()].has_value());
-				debug_assert!(self.variables[id.to_usize()].get_depth() <= self.depth);
-				if !marks[id.to_usize()] {
-					marks[id.to_usize()] = true;
-					let d = self.variables[id.to_usize()].get_depth();
+			if let ClauseRef::Long(cref) = cref {
+				self.bump_clause(cref);
+			}
+			for (id, negated) in cref.literals(&self.clauses).iter().map(|lit| lit.disassemble()) {
+				debug_assert!(self.assignment.has_value(id));
+				debug_assert!(self.assignment.get_depth(id) <= self.depth);
+				if marks.insert(id.to_usize()) {
+					let d = self.assignment.get_depth(id);
+					if d.to_usize() != 0 {
+						self.heuristic.bump(id);
+					}
 					if d == self.depth {
-						let ante = self.variables[id.to_usize()].get_ante();
-						if ante == usize::MAX {
-							if implicated != VariableId::MAX {
-								queue.push(self.variables[lits[implicated.to_usize()].id().to_usize()].get_ante());
-								lits.swap_remove(implicated.to_usize());
+						match self.assignment.get_ante(id) {
+							None => {
+								if implicated != VariableId::MAX {
+									queue.push(self.assignment.get_ante(lits[implicated.to_usize()].id()).unwrap());
+									lits.swap_remove(implicated.to_usize());
+								}
+								implicated = VariableId::from_usize(lits.len());
+								lits.push(Literal::new(id, negated));
+							}
+							Some(ante) if implicated != VariableId::MAX => queue.push(ante),
+							Some(_) => {
+								implicated = VariableId::from_usize(lits.len());
+								lits.push(Literal::new(id, negated));
 							}
-							implicated = VariableId::from_usize(lits.len());
-							lits.push(Literal::new(id, negated));
-						} else if implicated != VariableId::MAX {
-							queue.push(ante);
-						} else {
-							implicated = VariableId::from_usize(lits.len());
-							lits.push(Literal::new(id, negated));
 						}
 					} else if d.to_usize() != 0 {
 						lits.push(Literal::new(id, negated));
@@ -89,56 +210,160 @@ impl<T: fmt::Display> Problem<T> {
 			}
 			match queue.pop() {
 				None => break,
-				Some(t) => cid = t,
+				Some(next) => cref = next,
 			}
 		}
 		debug_assert!(implicated != VariableId::MAX);
-		self.minimize(&mut lits, marks);
-		lits
+		self.minimize(&mut lits, &mut marks);
+		let hints = match self.proof.as_ref() {
+			Some(proof) if proof.has_hints() => self.lrat_hints(conflict, &lits, &mut marks),
+			_ => Vec::new(),
+		};
+		marks.clear();
+		self.marks = marks;
+		self.analysis_queue = queue;
+		(lits, hints)
 	}
 
-	fn propagate_learned(&mut self, lits: ClauseLiteralVec) -> Option<usize> {
+	// every marked variable that is not part of the learned clause was resolved on (or removed by minimization), so the
+	// reasons of these variables in trail order turn the negated learned clause into the conflict
+	fn lrat_hints(&self, conflict: ClauseRef, lits: &[Literal], marks: &mut Marks) -> Vec<u64> {
+		for lit in lits {
+			marks.remove(lit.id().to_usize());
+		}
+		let antes: Vec<ClauseRef> = self
+			.applications
+			.iter()
+			.filter(|id| marks.contains(id.to_usize()))
+			.map(|&id| {
+				self
+					.assignment
+					.get_ante(id)
+					.expect("only implied variables are resolved on")
+			})
+			.collect();
+		let mut hints: Vec<u64> = antes
+			.iter()
+			.chain(std::iter::once(&conflict))
+			.flat_map(|cref| cref.literals(&self.clauses))
+			.filter(|lit| self.assignment.get_depth(lit.id()).to_usize() == 0)
+			.map(|lit| self.unit_ids[lit.id().to_usize()])
+			.collect();
+		hints.sort_unstable();
+		hints.dedup();
+		hints.extend(antes.iter().map(|cref| cref.id(&self.clauses, &self.variables)));
+		hints.push(conflict.id(&self.clauses, &self.variables));
+		hints
+	}
+
+	// the clause `cref` is falsified by the permanent assignments of depth 0
+	pub(super) fn log_empty_clause(&mut self, cref: ClauseRef) {
+		let id = self.new_clause_id();
+		let hints: Vec<u64> = cref
+			.literals(&self.clauses)
+			.iter()
+			.map(|lit| self.unit_ids[lit.id().to_usize()])
+			.chain(std::iter::once(cref.id(&self.clauses, &self.variables)))
+			.collect();
+		if let Some(proof) = self.proof.as_mut() {
+			proof.add(id, [], hints);
+		}
+	}
+
+	pub(super) fn new_clause_id(&mut self) -> u64 {
+		let id = self.next_clause_id;
+		self.next_clause_id += 1;
+		id
+	}
+
+	fn propagate_learned(&mut self, lits: ClauseLiteralVec, hints: Vec<u64>) -> Option<ClauseRef> {
+		let id = self.new_clause_id();
+		if let Some(proof) = self.proof.as_mut() {
+			proof.add(id, lits.iter().copied(), hints);
+		}
+		self.stats.learned_clauses += 1;
 		if lits.len() == 1 {
+			self.stats.learned_glue += 1;
+			self.update_restarts(1);
 			let lit = lits[0];
-			debug_assert!(self.variables[lit.id().to_usize()].has_value());
-			debug_assert!(self.variables[lit.id().to_usize()].get_depth() == self.depth);
+			debug_assert!(self.assignment.has_value(lit.id()));
+			debug_assert!(self.assignment.get_depth(lit.id()) == self.depth);
 			self.restart();
 			self.conflict_lens.add(0);
-			debug_assert!(!self.variables[lit.id().to_usize()].has_value());
-			self.variables[lit.id().to_usize()].set(!lit.negated(), self.depth, usize::MAX);
-			self.applications.push(lit.id());
-			let conflict = self.propagate();
-			self.active_variables -= self.applications.len();
-			self.applications.clear();
-			conflict
+			self.propagate_unit(lit, id)
 		} else {
-			let (backtrack, lit, clause) = Clause::from_learned(lits, &self.variables, self.depth);
-			self.depth = backtrack;
-			self.clauses.push(clause);
-			debug_assert!(self.variables[lit.id().to_usize()].has_value());
+			let mut lits = lits;
+			let (glue, watched) = prepare_learned(&mut lits, &self.assignment, &mut self.depth_stamps);
+			let lit = lits[watched[0]];
+			self.depth = self.assignment.get_depth(lits[watched[1]].id());
+			self.stats.learned_glue += glue.to_usize() as u64;
+			self.update_restarts(glue.to_usize());
+			debug_assert!(self.assignment.has_value(lit.id()));
 			self.backjump();
-			self
-				.conflict_lens
-				.add((self.clauses.last().unwrap().len() - 1).try_into().unwrap());
-			self
-				.clauses
-				.last()
-				.unwrap()
-				.notify_watched(self.clauses.len() - 1, &mut self.variables);
-			self.variables[lit.id().to_usize()].set(!lit.negated(), self.depth, self.clauses.len() - 1);
+			self.conflict_lens.add(lits.len() - 1);
+			let ante = if lits.len() == 2 {
+				let other = lits[watched[1]];
+				self.add_binary_clause([lit, other], id);
+				ClauseRef::Binary([lit, other])
+			} else {
+				let cref = self.clauses.push(id, &lits, glue, true, watched);
+				self.clauses.notify_watched(cref, &self.assignment, &mut self.variables);
+				self.add_learned(cref);
+				ClauseRef::Long(cref)
+			};
+			self.assignment.set(lit.id(), !lit.negated(), self.depth, Some(ante));
 			self.applications.push(lit.id());
+			self.heuristic.assigned(lit.id());
 			self.propagate()
 		}
 	}
 
-	fn subsumption_check(&self, vid: VariableId, marks: &mut Vec<bool>) -> bool {
-		for id in self.clauses[self.variables[vid.to_usize()].get_ante()]
-			.iter()
-			.map(|lit| lit.id())
-		{
-			if vid != id && !marks[id.to_usize()] && self.variables[id.to_usize()].get_depth().to_usize() != 0 {
-				if self.variables[id.to_usize()].get_ante() != usize::MAX && self.subsumption_check(id, marks) {
-					marks[id.to_usize()] = true;
+	// assigns `lit`, which is the unit clause `id`, at depth 0 and propagates it, which makes all resulting assignments
+	// permanent
+	pub(super) fn propagate_unit(&mut self, lit: Literal, id: u64) -> Option<ClauseRef> {
+		debug_assert!(self.depth.to_usize() == 0);
+		debug_assert!(!self.assignment.has_value(lit.id()));
+		self.assignment.set(lit.id(), !lit.negated(), self.depth, None);
+		self.unit_ids[lit.id().to_usize()] = id;
+		self.applications.push(lit.id());
+		self.heuristic.assigned(lit.id());
+		let conflict = self.propagate();
+		if self.proof.is_some() {
+			// the implied assignments must not depend on clauses that may be deleted later on
+			for ai in 1..self.applications.len() {
+				let vid = self.applications[ai];
+				let unit_id = self.new_clause_id();
+				let ante = self.assignment.get_ante(vid).expect("the assignment was implied");
+				let unit = Literal::new(vid, !self.assignment.get_value(vid));
+				let hints: Vec<u64> = ante
+					.literals(&self.clauses)
+					.iter()
+					.filter(|lit| lit.id() != vid)
+					.map(|lit| self.unit_ids[lit.id().to_usize()])
+					.chain(std::iter::once(ante.id(&self.clauses, &self.variables)))
+					.collect();
+				if let Some(proof) = self.proof.as_mut() {
+					proof.add(unit_id, [unit], hints);
+				}
+				self.unit_ids[vid.to_usize()] = unit_id;
+			}
+		}
+		// permanent assignments need no reasons, which would otherwise keep their clauses from being deleted and point
+		// into the arena after it is compacted
+		for &vid in self.applications.iter() {
+			self.assignment.set_ante(vid, None);
+		}
+		self.active_variables -= self.applications.len();
+		self.applications.clear();
+		conflict
+	}
+
+	fn subsumption_check(&self, vid: VariableId, marks: &mut Marks) -> bool {
+		let ante = self.assignment.get_ante(vid).unwrap();
+		for id in ante.literals(&self.clauses).iter().map(|lit| lit.id()) {
+			if vid != id && !marks.contains(id.to_usize()) && self.assignment.get_depth(id).to_usize() != 0 {
+				if self.assignment.get_ante(id).is_some() && self.subsumption_check(id, marks) {
+					marks.insert(id.to_usize());
 				} else {
 					return false;
 				}
@@ -147,12 +372,13 @@ impl<T: fmt::Display> Problem<T> {
 		true
 	}
 
-	pub fn minimize(&self, lits: &mut ClauseLiteralVec, mut marks: Vec<bool>) {
+	pub fn minimize(&self, lits: &mut ClauseLiteralVec, marks: &mut Marks) {
 		let mut i = 0;
 		while i < lits.len() {
-			let var = &self.variables[lits[i].id().to_usize()];
-			if var.get_ante() != usize::MAX && var.get_depth() != self.depth {
-				if var.get_depth().to_usize() == 0 || self.subsumption_check(lits[i].id(), &mut marks) {
+			let id = lits[i].id();
+			let depth = self.assignment.get_depth(id);
+			if self.assignment.get_ante(id).is_some() && depth != self.depth {
+				if depth.to_usize() == 0 || self.subsumption_check(id, marks) {
 					lits.swap_remove(i);
 				} else {
 					i += 1;
@@ -161,102 +387,123 @@ impl<T: fmt::Display> Problem<T> {
 				i += 1;
 			}
 		}
-		lits.shrink_to_fit();
 	}
 
 	// backjump applications down to depth
 	fn backjump(&mut self) {
-		loop {
-			if self.applications.is_empty() {
+		while let Some(&id) = self.applications.last() {
+			if self.assignment.get_depth(id) <= self.depth {
 				break;
 			}
-			let var = &mut self.variables[self.applications.last().unwrap().to_usize()];
-			if self.depth == var.get_depth() {
-				break;
-			}
-			var.unset();
+			self.assignment.unset(id);
 			self.applications.pop();
+			self.heuristic.unassigned(id);
 		}
 	}
 
 	// resets depth to 0 and unsets all variables
-	fn restart(&mut self) {
+	pub(super) fn restart(&mut self) {
+		if self.solution == SolverResult::Sat {
+			// the model is lost
+			self.solution = SolverResult::Unknown;
+		}
 		self.depth = VariableId::from_usize(0);
 		for id in self.applications.drain(..) {
-			self.variables[id.to_usize()].unset();
+			self.assignment.unset(id);
+			self.heuristic.unassigned(id);
 		}
 	}
 
-	fn update_q(&mut self, conflict: &Option<usize>) {
-		let multiplier = if conflict.is_some() {
-			self.alpha
-		} else {
-			0.9 * self.alpha
-		};
-		let nalpha = 1.0 - self.alpha;
-		for id in self.plays.drain(..) {
-			let q = self.variables[id.to_usize()].q_mut();
-			*q = nalpha * *q + multiplier / ((self.num_conflicts - self.last_conflict[id.to_usize()] + 1) as f64);
-			// FIXME: explicit conversion is fugly
-		}
+	fn choose(&mut self) {
+		let choice = self
+			.heuristic
+			.next(&self.assignment)
+			.expect("an unassigned variable is left");
+		let phase = self.assignment.get_phase(choice);
+		self.decide(choice, phase);
 	}
 
-	fn choose(&mut self) {
-		let choice = VariableId::from_usize(
-			self
-				.variables
-				.iter()
-				.enumerate()
-				.filter(|(_, var)| !var.has_value())
-				.max_by(|(_, a), (_, b)| a.q().partial_cmp(b.q()).unwrap())
-				.unwrap()
-				.0,
-		); // FIXME: get rid of the conversion
-		self.plays.push(choice);
+	fn decide(&mut self, id: VariableId, value: bool) {
 		self.depth = VariableId::from_usize(self.depth.to_usize() + 1);
-		self.variables[choice.to_usize()].enable(self.depth);
-		self.applications.push(choice);
+		self.assignment.set(id, value, self.depth, None);
+		self.applications.push(id);
+		self.heuristic.assigned(id);
+		self.stats.decisions += 1;
+		self.stats.max_depth = self.stats.max_depth.max(self.depth.to_usize());
 	}
 
-	fn propagate(&mut self) -> Option<usize> {
+	pub(super) fn propagate(&mut self) -> Option<ClauseRef> {
 		debug_assert!(!self.applications.is_empty());
 		let mut ai = self.applications.len() - 1;
 		let mut id = self.applications[ai];
 		loop {
-			debug_assert!(self.variables[id.to_usize()].has_value());
-			let val = self.variables[id.to_usize()].get_value();
-			if !self.variables[id.to_usize()].get_clauses(val).is_empty() {
-				let mut ci: usize = 0;
-				let mut cid = self.variables[id.to_usize()].get_clauses(val)[ci];
-				loop {
-					let clause = &mut self.clauses[cid];
-					match clause.apply(cid, &mut self.variables) {
-						Apply::Continue => {}
-						Apply::Unsat => return Some(cid),
+			debug_assert!(self.assignment.has_value(id));
+			self.stats.propagations += 1;
+			let val = self.assignment.get_value(id);
+			// binary clauses are cheaper to propagate and are therefore handled first
+			let falsified = Literal::new(id, val);
+			for i in 0..self.variables[id.to_usize()].get_implications(val).len() {
+				let Implication { literal, .. } = self.variables[id.to_usize()].get_implications(val)[i];
+				match self.assignment.literal_value(literal) {
+					None => {
+						let ante = ClauseRef::Binary([literal, falsified]);
+						self
+							.assignment
+							.set(literal.id(), !literal.negated(), self.depth, Some(ante));
+						self.applications.push(literal.id());
+						self.heuristic.assigned(literal.id());
+					}
+					Some(false) => return Some(ClauseRef::Binary([literal, falsified])),
+					Some(true) => {}
+				}
+			}
+			// the watch list is compacted in place, dropping the watches that moved to other literals
+			let mut watchers = std::mem::take(self.variables[id.to_usize()].get_watchers(val));
+			let mut kept = 0;
+			let mut conflict = None;
+			let mut wi = 0;
+			while wi < watchers.len() {
+				let mut watcher = watchers[wi];
+				wi += 1;
+				if self.assignment.literal_value(watcher.blocker) != Some(true) {
+					if self.clauses.is_deleted(watcher.cid) {
+						// watches of deleted clauses are only dropped once they are encountered
+						continue;
+					}
+					match self
+						.clauses
+						.apply(watcher.cid, falsified, &self.assignment, &mut self.variables)
+					{
+						Apply::Moved => continue,
+						Apply::Satisfied(lit) => watcher.blocker = lit,
 						Apply::Unit(lit) => {
-							debug_assert!(!self.variables[lit.id().to_usize()].has_value());
-							self.variables[lit.id().to_usize()].set(!lit.negated(), self.depth, cid);
-							clause.update_glue(&self.variables, self.depth);
+							debug_assert!(!self.assignment.has_value(lit.id()));
+							let ante = Some(ClauseRef::Long(watcher.cid));
+							self.assignment.set(lit.id(), !lit.negated(), self.depth, ante);
+							self
+								.clauses
+								.update_glue(watcher.cid, &self.assignment, &mut self.depth_stamps);
 							self.applications.push(lit.id());
-							self.plays.push(lit.id());
-						}
-					}
-					let clauses = &self.variables[id.to_usize()].get_clauses(val);
-					if let Some(&val) = clauses.get(ci) {
-						if val == cid {
-							ci += 1;
-							if let Some(&val) = clauses.get(ci) {
-								cid = val;
-							} else {
-								break;
-							}
-						} else {
-							cid = val;
+							self.heuristic.assigned(lit.id());
+							watcher.blocker = lit;
 						}
-					} else {
-						break;
+						Apply::Unsat => conflict = Some(ClauseRef::Long(watcher.cid)),
 					}
 				}
+				watchers[kept] = watcher;
+				kept += 1;
+				if conflict.is_some() {
+					watchers.copy_within(wi.., kept);
+					kept += watchers.len() - wi;
+					break;
+				}
+			}
+			watchers.truncate(kept);
+			let list = self.variables[id.to_usize()].get_watchers(val);
+			debug_assert!(list.is_empty());
+			*list = watchers;
+			if conflict.is_some() {
+				return conflict;
 			}
 			ai += 1;
 			if ai < self.applications.len() {
@@ -268,23 +515,10 @@ impl<T: fmt::Display> Problem<T> {
 		None
 	}
 
-	fn delete_clauses(&mut self) {
-		self.gc_count += 1;
-		//println!("[GC #{}]", self.gc_count);
-		//let old = self.clauses.len();
-		for var in self.variables.iter_mut() {
-			var.clear_watched();
-		}
-		self.clauses[self.irreducible..].sort_by_key(|clause| clause.get_glue());
-		self.irreducible += self.clauses[self.irreducible..]
-			.iter()
-			.take_while(|clause| clause.get_glue().to_usize() == 2)
-			.count();
-		let truncate = self.clauses.len() - (self.clauses.len() - self.irreducible) / 2;
-		self.clauses.truncate(truncate);
-		for (cid, ref clause) in self.clauses.iter_mut().enumerate() {
-			clause.notify_watched(cid, &mut self.variables);
-		}
-		//println!("[GC {} -> {}]", old, self.clauses.len());
+	/// Adds the binary clause `literals` with the proof id `id` to the implication lists of both of its literals
+	pub(super) fn add_binary_clause(&mut self, literals: [Literal; 2], id: u64) {
+		let [a, b] = literals;
+		self.variables[a.id().to_usize()].add_implication(a.negated(), Implication { literal: b, id });
+		self.variables[b.id().to_usize()].add_implication(b.negated(), Implication { literal: a, id });
 	}
 }
@@ -0,0 +1,327 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::eliminate::Reconstruction;
+use crate::SolverResult;
+use crate::cnf::{Assignment, ClauseLiteralVec, Literal, Proof, VariableId};
+use crate::util::Marks;
+
+// transitive reduction gives up after visiting this many edges of the binary implication graph
+const MAX_TRANSITIVE_STEPS: usize = 1_000_000;
+
+const UNVISITED: usize = usize::MAX;
+
+// the binary implication graph, in which each binary clause {a, b} yields the edges ¬a → b and ¬b → a, both of which
+// are labeled with the index of the clause
+struct Graph {
+	edges: Vec<Vec<(Literal, usize)>>,
+}
+
+impl Graph {
+	fn new(variables: usize, clauses: &[ClauseLiteralVec]) -> Graph {
+		let mut edges = vec![Vec::new(); 2 * variables];
+		for (ci, clause) in clauses.iter().enumerate() {
+			if let [a, b] = clause[..] {
+				edges[a.negate().index()].push((b, ci));
+				edges[b.negate().index()].push((a, ci));
+			}
+		}
+		Graph { edges }
+	}
+
+	// Tarjan's algorithm without recursion, which returns the strongly connected component of each literal
+	fn components(&self) -> Vec<usize> {
+		let len = self.edges.len();
+		let mut order = vec![UNVISITED; len];
+		let mut lowlinks = vec![0; len];
+		let mut components = vec![UNVISITED; len];
+		let mut stack = Vec::new();
+		// the literals whose edges are being visited, together with the next edge to visit
+		let mut calls: Vec<(usize, usize)> = Vec::new();
+		let mut visited = 0;
+		let mut count = 0;
+		for root in 0..len {
+			if order[root] != UNVISITED {
+				continue;
+			}
+			order[root] = visited;
+			lowlinks[root] = visited;
+			visited += 1;
+			stack.push(root);
+			calls.push((root, 0));
+			while let Some(&(node, edge)) = calls.last() {
+				if let Some(&(succ, _)) = self.edges[node].get(edge) {
+					calls.last_mut().unwrap().1 += 1;
+					let succ = succ.index();
+					if order[succ] == UNVISITED {
+						order[succ] = visited;
+						lowlinks[succ] = visited;
+						visited += 1;
+						stack.push(succ);
+						calls.push((succ, 0));
+					} else if components[succ] == UNVISITED {
+						// still on the stack
+						lowlinks[node] = lowlinks[node].min(order[succ]);
+					}
+					continue;
+				}
+				calls.pop();
+				if let Some(&(parent, _)) = calls.last() {
+					lowlinks[parent] = lowlinks[parent].min(lowlinks[node]);
+				}
+				if lowlinks[node] == order[node] {
+					loop {
+						let member = stack.pop().expect("the component is on the stack");
+						components[member] = count;
+						if member == node {
+							break;
+						}
+					}
+					count += 1;
+				}
+			}
+		}
+		components
+	}
+
+	// Derives the clause {¬lit, representative} for every literal of the component of `representative` by a backward
+	// breadth-first search, each from the edge to an earlier literal and the clause of that literal
+	fn derive(
+		&self,
+		representative: Literal,
+		components: &[usize],
+		ids: &[u64],
+		equivalences: &mut Equivalences,
+		next_id: &mut u64,
+		proof: &mut Proof,
+	) {
+		let component = components[representative.index()];
+		let mut queue = VecDeque::from([representative]);
+		let reached = &mut equivalences.reached;
+		reached.clear();
+		reached.insert(representative.index());
+		while let Some(lit) = queue.pop_front() {
+			// each edge ¬lit → succ has the contrapositive ¬succ → lit
+			for &(succ, ci) in self.edges[lit.negate().index()].iter() {
+				let pred = succ.negate();
+				if components[pred.index()] != component || !reached.insert(pred.index()) {
+					continue;
+				}
+				equivalences.ids[pred.index()] = if lit == representative {
+					ids[ci]
+				} else {
+					let id = *next_id;
+					*next_id += 1;
+					let mut clause = ClauseLiteralVec::from_slice(&[pred.negate(), representative]);
+					clause.dedup();
+					proof.add(id, clause.iter().copied(), [equivalences.ids[lit.index()], ids[ci]]);
+					equivalences.derived.push((id, clause));
+					id
+				};
+				queue.push_back(pred);
+			}
+		}
+	}
+}
+
+// the proof ids of the clauses {¬lit, representative}, indexed by literal, of which those that are not input clauses
+// are deleted from the proof again once the substitution is done, unless they justify a substituted variable
+struct Equivalences {
+	ids: Vec<u64>,
+	derived: Vec<(u64, ClauseLiteralVec)>,
+	// the literals reached by the current search
+	reached: Marks,
+}
+
+// Equivalent literal substitution: the literals of each strongly connected component of the binary implication graph
+// imply each other, so all of them are replaced by one representative. Substituted variables no longer occur in the
+// clauses and are assigned an arbitrary value like eliminated variables, which `reconstruction` corrects by means of
+// the equivalence. Frozen variables are kept and preferred as representatives. Clauses are kept parallel to `ids` like
+// in `precompute`, and duplicate and tautological clauses are removed. Substitution may produce units, so the result
+// should be propagated once more.
+pub fn substitute(
+	assignment: &mut Assignment,
+	frozen: &[VariableId],
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	next_id: &mut u64,
+	proof: &mut Option<Proof>,
+	reconstruction: &mut Reconstruction,
+) -> SolverResult {
+	debug_assert_eq!(clauses.len(), ids.len());
+	let graph = Graph::new(assignment.len(), clauses);
+	let components = graph.components();
+	// the literals grouped by their components
+	let mut members: Vec<Literal> = (0..components.len()).map(Literal::from_raw).collect();
+	members.sort_by_key(|lit| components[lit.index()]);
+	let mut skipped = Marks::new();
+	for id in frozen {
+		skipped.insert(id.to_usize());
+	}
+
+	// each literal is mapped to the representative of its component, and the proof receives the clauses
+	// {¬lit, representative} that justify the substitution
+	let mut representatives: Vec<Literal> = (0..components.len()).map(Literal::from_raw).collect();
+	let mut equivalences = Equivalences {
+		ids: vec![0; components.len()],
+		derived: Vec::new(),
+		reached: Marks::new(),
+	};
+	for literals in members.chunk_by(|a, b| components[a.index()] == components[b.index()]) {
+		if literals.len() < 2 {
+			continue;
+		}
+		let component = components[literals[0].index()];
+		let opposite = components[literals[0].negate().index()];
+		if opposite == component {
+			// x and ¬x imply each other
+			if let Some(proof) = proof.as_mut() {
+				let lit = literals[0];
+				let mut units = [0; 2];
+				for (unit, root) in units.iter_mut().zip([lit, lit.negate()]) {
+					graph.derive(root, &components, ids, &mut equivalences, next_id, proof);
+					*unit = equivalences.ids[root.negate().index()];
+				}
+				proof.add(*next_id, [], units);
+			}
+			return SolverResult::Unsat;
+		}
+		if opposite < component {
+			// the component was handled together with its opposite
+			continue;
+		}
+		let representative = *literals
+			.iter()
+			.min_by_key(|lit| (!skipped.contains(lit.id().to_usize()), lit.id()))
+			.expect("the component is not empty");
+		for &lit in literals.iter() {
+			representatives[lit.index()] = representative;
+			representatives[lit.negate().index()] = representative.negate();
+		}
+		if let Some(proof) = proof.as_mut() {
+			for root in [representative, representative.negate()] {
+				graph.derive(root, &components, ids, &mut equivalences, next_id, proof);
+			}
+		}
+	}
+	// frozen variables keep occurring in the clauses, where the binary clauses that remain after the substitution
+	// still make them equivalent to their representatives
+	for (index, representative) in representatives.iter_mut().enumerate() {
+		if skipped.contains(Literal::from_raw(index).id().to_usize()) {
+			*representative = Literal::from_raw(index);
+		}
+	}
+
+	let mut removed = vec![false; clauses.len()];
+	// the replaced clauses may still be needed to justify the equivalences, so they are deleted from the proof at the
+	// end
+	let mut replaced = Vec::new();
+	for (ci, clause) in clauses.iter_mut().enumerate() {
+		if clause.iter().all(|lit| representatives[lit.index()] == *lit) {
+			continue;
+		}
+		let original = std::mem::take(clause);
+		let hints: Vec<u64> = original
+			.iter()
+			.filter(|lit| representatives[lit.index()] != **lit)
+			.map(|lit| equivalences.ids[lit.index()])
+			.chain(std::iter::once(ids[ci]))
+			.collect();
+		clause.extend(original.iter().map(|lit| representatives[lit.index()]));
+		clause.sort();
+		clause.dedup();
+		removed[ci] = clause.windows(2).any(|pair| pair[0].id() == pair[1].id());
+		if let Some(proof) = proof.as_mut() {
+			replaced.push((ids[ci], original));
+			if !removed[ci] {
+				proof.add(*next_id, clause.iter().copied(), hints);
+				ids[ci] = *next_id;
+				*next_id += 1;
+			}
+		}
+	}
+	// the clauses of the equivalences stay in the proof, as incremental calls may restore them
+	let mut kept = HashSet::new();
+	for (index, &representative) in representatives.iter().enumerate() {
+		let lit = Literal::from_raw(index);
+		if !lit.negated() && representative != lit {
+			let ids = [equivalences.ids[lit.negate().index()], equivalences.ids[lit.index()]];
+			kept.extend(ids);
+			reconstruction.push_equivalence(lit, representative, ids);
+			assignment.set(lit.id(), false, VariableId::from_usize(0), None);
+		}
+	}
+	if let Some(proof) = proof.as_mut() {
+		for (id, clause) in replaced.into_iter().chain(equivalences.derived) {
+			if !kept.contains(&id) {
+				proof.delete(id, clause);
+			}
+		}
+	}
+
+	// duplicates are next to each other once the clauses are sorted, and the first one of them is kept
+	let mut order: Vec<usize> = (0..clauses.len()).filter(|&ci| !removed[ci]).collect();
+	order.sort_by(|&a, &b| clauses[a].cmp(&clauses[b]).then(a.cmp(&b)));
+	for pair in order.windows(2) {
+		if clauses[pair[0]] == clauses[pair[1]] {
+			removed[pair[1]] = true;
+			if let Some(proof) = proof.as_mut() {
+				proof.delete(ids[pair[1]], clauses[pair[1]].iter().copied());
+			}
+		}
+	}
+	super::retain(clauses, ids, &removed);
+	if clauses.is_empty() {
+		SolverResult::Sat
+	} else {
+		SolverResult::Unknown
+	}
+}
+
+// Transitive reduction of the binary implication graph: a binary clause is removed if its implication also follows
+// from a path of other binary clauses. Returns the number of removed clauses.
+pub fn reduce_transitively(
+	variables: usize,
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	proof: &mut Option<Proof>,
+) -> u64 {
+	let graph = Graph::new(variables, clauses);
+	let mut removed = vec![false; clauses.len()];
+	let mut count = 0;
+	let mut steps = 0;
+	let mut reached = Marks::new();
+	let mut stack = Vec::new();
+	'clauses: for (ci, clause) in clauses.iter().enumerate() {
+		let [a, b] = clause[..] else {
+			continue;
+		};
+		reached.clear();
+		stack.clear();
+		stack.push(a.negate());
+		reached.insert(a.negate().index());
+		while let Some(lit) = stack.pop() {
+			for &(succ, cj) in graph.edges[lit.index()].iter() {
+				if cj == ci || removed[cj] {
+					continue;
+				}
+				steps += 1;
+				if succ == b {
+					removed[ci] = true;
+					count += 1;
+					if let Some(proof) = proof.as_mut() {
+						proof.delete(ids[ci], clause.iter().copied());
+					}
+					continue 'clauses;
+				}
+				if reached.insert(succ.index()) {
+					stack.push(succ);
+				}
+			}
+			if steps > MAX_TRANSITIVE_STEPS {
+				break 'clauses;
+			}
+		}
+	}
+	super::retain(clauses, ids, &removed);
+	count
+}
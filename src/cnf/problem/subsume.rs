@@ -0,0 +1,277 @@
+use std::cmp::Reverse;
+use std::fmt;
+
+use crate::cnf::{ArenaRef, ClauseLiteralVec, ClauseRef, Literal, Problem, Proof, VariableId};
+use crate::util::Marks;
+
+// a summary of the variables of a clause, which contains the summaries of all clauses that subsume or strengthen it
+fn signature(literals: &[Literal]) -> u64 {
+	literals.iter().fold(0, |signature, lit| {
+		signature | 1 << (lit.id().to_usize() % u64::BITS as usize)
+	})
+}
+
+// how a clause relates to the clause whose literals are marked
+enum Relation {
+	Unrelated,
+	Subsumes,
+	// resolving both clauses yields the marked clause without this literal, which is therefore redundant
+	Strengthens(Literal),
+}
+
+fn relation(subsumer: &[Literal], marks: &Marks) -> Relation {
+	let mut negated = None;
+	for &lit in subsumer {
+		if marks.contains(lit.index()) {
+			continue;
+		}
+		let negation = lit.negate();
+		if negated.is_none() && marks.contains(negation.index()) {
+			negated = Some(negation);
+		} else {
+			return Relation::Unrelated;
+		}
+	}
+	match negated {
+		None => Relation::Subsumes,
+		Some(lit) => Relation::Strengthens(lit),
+	}
+}
+
+// Backward subsumption and self-subsuming resolution: each clause, shortest first, removes the clauses it subsumes and
+// the literals it makes redundant, where candidates are found through the occurrence lists of its least frequent
+// variable. The clauses are kept parallel to `ids` like in `precompute`, and must not contain assigned variables.
+// Strengthened clauses may become units, so the result should be propagated once more. Returns the number of removed
+// and of strengthened clauses.
+pub fn subsume(
+	variables: usize,
+	clauses: &mut Vec<ClauseLiteralVec>,
+	ids: &mut Vec<u64>,
+	next_id: &mut u64,
+	proof: &mut Option<Proof>,
+) -> (u64, u64) {
+	debug_assert_eq!(clauses.len(), ids.len());
+	// occurrence lists, indexed by literal, which may still refer to removed clauses
+	let mut occurrences = vec![Vec::new(); 2 * variables];
+	for (ci, clause) in clauses.iter().enumerate() {
+		for lit in clause.iter() {
+			occurrences[lit.index()].push(ci);
+		}
+	}
+	let mut signatures: Vec<u64> = clauses.iter().map(|clause| signature(clause)).collect();
+	let mut removed = vec![false; clauses.len()];
+	// popped from the back, where strengthened clauses are tried again
+	let mut queue: Vec<usize> = (0..clauses.len()).collect();
+	queue.sort_by_key(|&ci| Reverse(clauses[ci].len()));
+
+	let (mut subsumed, mut strengthened) = (0, 0);
+	let mut marks = Marks::new();
+	let mut candidates = Vec::new();
+	while let Some(ci) = queue.pop() {
+		if removed[ci] {
+			continue;
+		}
+		let occurrences_of = |lit: Literal| [lit.index(), lit.negate().index()];
+		let rarest = clauses[ci]
+			.iter()
+			.copied()
+			.min_by_key(|&lit| {
+				occurrences_of(lit)
+					.map(|index| occurrences[index].len())
+					.iter()
+					.sum::<usize>()
+			})
+			.expect("clauses are not empty");
+		candidates.clear();
+		for index in occurrences_of(rarest) {
+			candidates.extend(occurrences[index].iter().copied());
+		}
+		for &di in candidates.iter() {
+			if di == ci || removed[di] || clauses[di].len() < clauses[ci].len() || signatures[ci] & !signatures[di] != 0 {
+				continue;
+			}
+			marks.clear();
+			for lit in clauses[di].iter() {
+				marks.insert(lit.index());
+			}
+			match relation(&clauses[ci], &marks) {
+				Relation::Unrelated => {}
+				Relation::Subsumes => {
+					removed[di] = true;
+					subsumed += 1;
+					if let Some(proof) = proof.as_mut() {
+						proof.delete(ids[di], clauses[di].iter().copied());
+					}
+				}
+				// opposite unit clauses are left to the propagation afterwards, which derives the empty clause
+				Relation::Strengthens(_) if clauses[di].len() == 1 => {}
+				Relation::Strengthens(lit) => {
+					let original = proof.as_ref().map(|_| clauses[di].clone());
+					clauses[di].retain(|other| *other != lit);
+					if let (Some(proof), Some(original)) = (proof.as_mut(), original) {
+						proof.add(*next_id, clauses[di].iter().copied(), [ids[ci], ids[di]]);
+						proof.delete(ids[di], original);
+						ids[di] = *next_id;
+						*next_id += 1;
+					}
+					signatures[di] = signature(&clauses[di]);
+					occurrences[lit.index()].retain(|&other| other != di);
+					strengthened += 1;
+					queue.push(di);
+				}
+			}
+		}
+	}
+
+	super::retain(clauses, ids, &removed);
+	(subsumed, strengthened)
+}
+
+impl<T: fmt::Display> Problem<T> {
+	// Forward subsumption of the learned clauses by all clauses, which happens after a restart, so that only the
+	// permanent assignments remain. Every clause is watched by just one of its literals, the one that occurs least
+	// often, which is enough to find all clauses that subsume or strengthen a given one. Returns the conflict if a
+	// strengthened unit clause turns out to be falsified. Deleted clauses are left in the tiers.
+	pub(super) fn subsume_learned(&mut self) -> Option<ClauseRef> {
+		self.restart();
+		let mut targets: Vec<ArenaRef> = self
+			.clauses
+			.iter()
+			.filter(|&cref| self.clauses.is_learned(cref))
+			.collect();
+		let mut subsumers: Vec<ClauseRef> = self.clauses.iter().map(ClauseRef::Long).collect();
+		for (id, var) in self.variables.iter().enumerate() {
+			for negated in [false, true] {
+				let lit = Literal::new(VariableId::from_usize(id), negated);
+				subsumers.extend(
+					var
+						.get_implications(negated)
+						.iter()
+						.filter(|implication| lit < implication.literal)
+						.map(|implication| ClauseRef::Binary([lit, implication.literal])),
+				);
+			}
+		}
+		let mut counts = vec![0usize; 2 * self.variables.len()];
+		for subsumer in subsumers.iter() {
+			for lit in subsumer.literals(&self.clauses) {
+				counts[lit.index()] += 1;
+			}
+		}
+		let mut watches: Vec<Vec<(u64, ClauseRef)>> = vec![Vec::new(); 2 * self.variables.len()];
+		for subsumer in subsumers {
+			let literals = subsumer.literals(&self.clauses);
+			let watched = literals
+				.iter()
+				.min_by_key(|lit| counts[lit.index()])
+				.expect("clauses are not empty");
+			watches[watched.index()].push((signature(literals), subsumer));
+		}
+
+		// short clauses are more likely to subsume others, which are then not considered anymore
+		targets.sort_by_key(|&cref| self.clauses.literals(cref).len());
+		let mut marks = Marks::new();
+		for cref in targets {
+			let literals = self.clauses.literals(cref);
+			if literals
+				.iter()
+				.any(|&lit| self.assignment.literal_value(lit) == Some(true))
+			{
+				continue;
+			}
+			let target = signature(literals);
+			marks.clear();
+			for lit in literals {
+				marks.insert(lit.index());
+			}
+			let mut found = None;
+			'search: for &lit in literals {
+				for watched in [lit, lit.negate()] {
+					for &(signature, subsumer) in watches[watched.index()].iter() {
+						let deleted = matches!(subsumer, ClauseRef::Long(other) if self.clauses.is_deleted(other));
+						if signature & !target != 0 || subsumer == ClauseRef::Long(cref) || deleted {
+							continue;
+						}
+						let subsuming = subsumer.literals(&self.clauses);
+						if subsuming.len() > literals.len() {
+							continue;
+						}
+						match relation(subsuming, &marks) {
+							Relation::Unrelated => {}
+							relation => {
+								found = Some((relation, subsumer.id(&self.clauses, &self.variables)));
+								break 'search;
+							}
+						}
+					}
+				}
+			}
+			match found {
+				None => {}
+				Some((Relation::Subsumes, _)) => {
+					self.stats.subsumed_clauses += 1;
+					self.delete_learned(cref);
+				}
+				Some((Relation::Strengthens(lit), id)) => {
+					self.stats.strengthened_clauses += 1;
+					if let Some(conflict) = self.strengthen_learned(cref, lit, id) {
+						return Some(conflict);
+					}
+				}
+				Some((Relation::Unrelated, _)) => unreachable!(),
+			}
+		}
+		None
+	}
+
+	fn delete_learned(&mut self, cref: ArenaRef) {
+		if self.clauses.get_glue(cref).to_usize() <= self.config.core_glue as usize {
+			self.stats.core_clauses -= 1;
+		}
+		if let Some(proof) = self.proof.as_mut() {
+			proof.delete(self.clauses.id(cref), self.clauses.literals(cref).iter().copied());
+		}
+		self.clauses.delete(cref);
+	}
+
+	// replaces the learned clause `cref` by itself without `lit`, which the clause `by` makes redundant, and without the
+	// literals that are permanently false
+	fn strengthen_learned(&mut self, cref: ArenaRef, lit: Literal, by: u64) -> Option<ClauseRef> {
+		let mut hints = Vec::new();
+		let mut literals = ClauseLiteralVec::new();
+		for &other in self.clauses.literals(cref) {
+			if other == lit {
+				continue;
+			}
+			match self.assignment.literal_value(other) {
+				None => literals.push(other),
+				Some(value) => {
+					debug_assert!(!value);
+					hints.push(self.unit_ids[other.id().to_usize()]);
+				}
+			}
+		}
+		hints.extend([by, self.clauses.id(cref)]);
+		let glue = self.clauses.get_glue(cref);
+		let id = self.new_clause_id();
+		if let Some(proof) = self.proof.as_mut() {
+			proof.add(id, literals.iter().copied(), hints);
+		}
+		self.delete_learned(cref);
+		// the clause would have been falsified before
+		debug_assert!(!literals.is_empty());
+		match literals.len() {
+			1 => return self.propagate_unit(literals[0], id),
+			2 => self.add_binary_clause([literals[0], literals[1]], id),
+			len => {
+				let glue = glue.min(VariableId::from_usize(len));
+				let cref = self.clauses.push(id, &literals, glue, true, [0, 1]);
+				self
+					.clauses
+					.initialize_watched(cref, &self.assignment, &mut self.variables);
+				self.add_learned(cref);
+			}
+		}
+		None
+	}
+}
@@ -1,13 +1,17 @@
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 
-use crate::cnf::{ClauseLiteralVec, Literal, Problem, VariableId};
+use crate::cnf::{ClauseLiteralVec, Literal, Problem, Proof, SolverConfig, VariableId};
 
 #[derive(Debug)]
 pub struct ProblemBuilder<T: ::std::hash::Hash + ::std::cmp::Eq> {
 	names2index: HashMap<T, VariableId>,
 	names: Vec<T>,
 	clauses: Vec<ClauseLiteralVec>,
+	proof: Option<Proof>,
+	keep_original: bool,
+	frozen: Vec<VariableId>,
+	config: SolverConfig,
 }
 
 impl<T> ProblemBuilder<T>
@@ -19,6 +23,10 @@ where
 			names2index: HashMap::new(),
 			names: Vec::new(),
 			clauses: Vec::new(),
+			proof: None,
+			keep_original: false,
+			frozen: Vec::new(),
+			config: SolverConfig::default(),
 		}
 	}
 
@@ -40,11 +48,42 @@ where
 		self.names2index.reserve(additional);
 	}
 
+	/// Makes the problem write a proof for unsatisfiable results
+	pub fn set_proof(&mut self, proof: Proof) {
+		self.proof = Some(proof);
+	}
+
+	/// Makes the problem keep a copy of the clauses as they were given, so that models can be verified against them
+	pub fn keep_original_clauses(&mut self) {
+		self.keep_original = true;
+	}
+
+	/// Keeps the variable called `name` from being eliminated during preprocessing, creating it if necessary. This is
+	/// not needed for correctness, as eliminated variables still receive values in models and are restored once they
+	/// are used by incremental calls, but it avoids the cost of restoring them.
+	pub fn freeze(&mut self, name: T) {
+		let id = self.variable_id(name);
+		self.frozen.push(id);
+	}
+
+	/// Makes the problem use `config` instead of the default search parameters
+	pub fn set_config(&mut self, config: SolverConfig) {
+		self.config = config;
+	}
+
 	pub fn as_problem(self) -> Problem<T> {
-		Problem::new(self.names, self.clauses)
+		Problem::build(
+			self.names,
+			self.clauses,
+			self.proof,
+			self.keep_original,
+			&self.frozen,
+			self.config,
+		)
 	}
 
-	fn variable_id(&mut self, name: T) -> VariableId {
+	/// Returns the id of the variable called `name`, creating it if necessary
+	pub fn variable_id(&mut self, name: T) -> VariableId {
 		match self.names2index.entry(name) {
 			Entry::Vacant(vacant_entry) => {
 				let id = VariableId::from_usize(self.names.len());
@@ -56,6 +95,11 @@ where
 		}
 	}
 
+	/// The clauses added so far, in the order in which they were added
+	pub fn clauses(&self) -> &[ClauseLiteralVec] {
+		&self.clauses
+	}
+
 	pub fn variable_count(&self) -> usize {
 		self.names.len()
 	}
@@ -0,0 +1,247 @@
+use std::{fmt, io};
+
+use super::Literal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProofFormat {
+	/// Textual DRAT, as used by the SAT competition
+	Drat,
+	/// Binary DRAT, which is more compact and faster to write and read
+	BinaryDrat,
+	/// Textual LRAT, which lists the antecedents of each clause so that no search is needed for checking
+	Lrat,
+	/// Binary LRAT
+	BinaryLrat,
+}
+
+impl ProofFormat {
+	/// Whether clause additions need to list the ids of their antecedents
+	pub fn has_hints(&self) -> bool {
+		matches!(self, ProofFormat::Lrat | ProofFormat::BinaryLrat)
+	}
+}
+
+/// A sink for the clausal proof of an unsatisfiable result.
+///
+/// Variables are written as their id + 1, which is the numbering of the dimacs file they were parsed from (see
+/// `parser::dimacs::parse`). Clauses are identified by their position in the input, starting at 1, and derived clauses
+/// continue this numbering. Write errors do not interrupt solving; the first one is reported by `finish` instead.
+pub struct Proof {
+	writer: Box<dyn io::Write>,
+	format: ProofFormat,
+	last_id: u64,
+	error: Option<io::Error>,
+}
+
+impl Proof {
+	pub fn new(writer: impl io::Write + 'static, format: ProofFormat) -> Proof {
+		Proof {
+			writer: Box::new(writer),
+			format,
+			last_id: 0,
+			error: None,
+		}
+	}
+
+	pub fn format(&self) -> ProofFormat {
+		self.format
+	}
+
+	pub fn has_hints(&self) -> bool {
+		self.format.has_hints()
+	}
+
+	/// Records the addition of the clause `id`, which is implied by the clauses added so far. The `hints` are only
+	/// written for LRAT proofs; they list the clauses which become unit (and finally conflicting) when assuming the
+	/// negation of the new clause, in the order in which they do so.
+	pub fn add(&mut self, id: u64, literals: impl IntoIterator<Item = Literal>, hints: impl IntoIterator<Item = u64>) {
+		self.last_id = self.last_id.max(id);
+		let result = match self.format {
+			_ if self.error.is_some() => return,
+			ProofFormat::Drat => write_text_drat(&mut self.writer, false, literals),
+			ProofFormat::BinaryDrat => write_binary_drat(&mut self.writer, b'a', literals),
+			ProofFormat::Lrat => write_text_lrat(&mut self.writer, id, literals, hints),
+			ProofFormat::BinaryLrat => write_binary_lrat(&mut self.writer, id, literals, hints),
+		};
+		self.record(result);
+	}
+
+	/// Records that the clause `id` is not needed anymore
+	pub fn delete(&mut self, id: u64, literals: impl IntoIterator<Item = Literal>) {
+		self.last_id = self.last_id.max(id);
+		let result = match self.format {
+			_ if self.error.is_some() => return,
+			ProofFormat::Drat => write_text_drat(&mut self.writer, true, literals),
+			ProofFormat::BinaryDrat => write_binary_drat(&mut self.writer, b'd', literals),
+			ProofFormat::Lrat => writeln!(self.writer, "{} d {} 0", self.last_id, id),
+			ProofFormat::BinaryLrat => write_binary_lrat_deletion(&mut self.writer, id),
+		};
+		self.record(result);
+	}
+
+	/// Flushes the proof and reports the first error that occurred while writing it
+	pub fn finish(mut self) -> io::Result<()> {
+		if let Some(err) = self.error.take() {
+			return Err(err);
+		}
+		self.writer.flush()
+	}
+
+	fn record(&mut self, result: io::Result<()>) {
+		if let Err(err) = result {
+			self.error = Some(err);
+		}
+	}
+}
+
+impl fmt::Debug for Proof {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Proof")
+			.field("format", &self.format)
+			.field("last_id", &self.last_id)
+			.field("error", &self.error)
+			.finish_non_exhaustive()
+	}
+}
+
+fn write_text_literals(writer: &mut impl io::Write, literals: impl IntoIterator<Item = Literal>) -> io::Result<()> {
+	for lit in literals {
+		if lit.negated() {
+			write!(writer, "-")?;
+		}
+		write!(writer, "{} ", lit.id().to_usize() + 1)?;
+	}
+	write!(writer, "0")
+}
+
+fn write_text_drat(
+	writer: &mut impl io::Write,
+	deletion: bool,
+	literals: impl IntoIterator<Item = Literal>,
+) -> io::Result<()> {
+	if deletion {
+		write!(writer, "d ")?;
+	}
+	write_text_literals(writer, literals)?;
+	writeln!(writer)
+}
+
+fn write_text_lrat(
+	writer: &mut impl io::Write,
+	id: u64,
+	literals: impl IntoIterator<Item = Literal>,
+	hints: impl IntoIterator<Item = u64>,
+) -> io::Result<()> {
+	write!(writer, "{} ", id)?;
+	write_text_literals(writer, literals)?;
+	for hint in hints {
+		write!(writer, " {}", hint)?;
+	}
+	writeln!(writer, " 0")
+}
+
+// all numbers in binary proofs are mapped from x to 2|x| + (x < 0) and then written as a variable length quantity
+fn write_binary_literals(writer: &mut impl io::Write, literals: impl IntoIterator<Item = Literal>) -> io::Result<()> {
+	for lit in literals {
+		write_varint(writer, 2 * (lit.id().to_usize() as u64 + 1) + lit.negated() as u64)?;
+	}
+	writer.write_all(&[0])
+}
+
+fn write_binary_drat(
+	writer: &mut impl io::Write,
+	kind: u8,
+	literals: impl IntoIterator<Item = Literal>,
+) -> io::Result<()> {
+	writer.write_all(&[kind])?;
+	write_binary_literals(writer, literals)
+}
+
+fn write_binary_lrat(
+	writer: &mut impl io::Write,
+	id: u64,
+	literals: impl IntoIterator<Item = Literal>,
+	hints: impl IntoIterator<Item = u64>,
+) -> io::Result<()> {
+	writer.write_all(b"a")?;
+	write_varint(writer, 2 * id)?;
+	write_binary_literals(writer, literals)?;
+	for hint in hints {
+		write_varint(writer, 2 * hint)?;
+	}
+	writer.write_all(&[0])
+}
+
+fn write_binary_lrat_deletion(writer: &mut impl io::Write, id: u64) -> io::Result<()> {
+	writer.write_all(b"d")?;
+	write_varint(writer, 2 * id)?;
+	writer.write_all(&[0])
+}
+
+fn write_varint(writer: &mut impl io::Write, mut value: u64) -> io::Result<()> {
+	let mut buf = [0u8; 10];
+	let mut len = 0;
+	while value >= 0x80 {
+		buf[len] = (value as u8) | 0x80;
+		value >>= 7;
+		len += 1;
+	}
+	buf[len] = value as u8;
+	writer.write_all(&buf[..=len])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cnf::VariableId;
+	use crate::util::SharedBuffer;
+
+	fn literals() -> [Literal; 2] {
+		[
+			Literal::new(VariableId::from_usize(0), false),
+			Literal::new(VariableId::from_usize(99), true),
+		]
+	}
+
+	#[test]
+	fn text() {
+		let buffer = SharedBuffer::default();
+		let mut proof = Proof::new(buffer.clone(), ProofFormat::Drat);
+		proof.add(5, literals(), [1, 2]);
+		proof.delete(5, literals());
+		proof.add(6, [], [5]);
+		proof.finish().unwrap();
+		assert_eq!(buffer.take(), b"1 -100 0\nd 1 -100 0\n0\n");
+	}
+
+	#[test]
+	fn binary() {
+		let buffer = SharedBuffer::default();
+		let mut proof = Proof::new(buffer.clone(), ProofFormat::BinaryDrat);
+		proof.add(5, literals(), [1, 2]);
+		proof.delete(5, literals());
+		proof.finish().unwrap();
+		assert_eq!(buffer.take(), &[b'a', 2, 201, 1, 0, b'd', 2, 201, 1, 0]);
+	}
+
+	#[test]
+	fn text_lrat() {
+		let buffer = SharedBuffer::default();
+		let mut proof = Proof::new(buffer.clone(), ProofFormat::Lrat);
+		proof.add(5, literals(), [1, 2]);
+		proof.delete(3, literals());
+		proof.add(6, [], [5]);
+		proof.finish().unwrap();
+		assert_eq!(buffer.take(), b"5 1 -100 0 1 2 0\n5 d 3 0\n6 0 5 0\n");
+	}
+
+	#[test]
+	fn binary_lrat() {
+		let buffer = SharedBuffer::default();
+		let mut proof = Proof::new(buffer.clone(), ProofFormat::BinaryLrat);
+		proof.add(5, literals(), [1, 2]);
+		proof.delete(3, literals());
+		proof.finish().unwrap();
+		assert_eq!(buffer.take(), &[b'a', 10, 2, 201, 1, 0, 2, 4, 0, b'd', 6, 0]);
+	}
+}
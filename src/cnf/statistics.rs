@@ -0,0 +1,124 @@
+use std::io;
+use std::time::Duration;
+
+use crate::util::DurationWrapper;
+
+/// Counters and timings that describe the work done by a `Problem` so far
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+	/// The number of branching decisions
+	pub decisions: u64,
+	/// The number of assignments whose consequences were propagated
+	pub propagations: u64,
+	/// The number of conflicts that were analyzed
+	pub conflicts: u64,
+	/// The number of restarts of the search
+	pub restarts: u64,
+	/// The number of rounds in which learned clauses were deleted
+	pub reductions: u64,
+	/// The number of variables removed by elimination or substitution that no incremental call has restored yet
+	pub eliminated_variables: u64,
+	/// The number of variables that were substituted by an equivalent literal, whether or not they were restored since
+	pub equivalent_variables: u64,
+	/// The number of binary clauses that were removed by transitive reduction during preprocessing
+	pub transitive_binaries: u64,
+	/// The number of clauses that were removed for being subsumed, during preprocessing and among learned clauses
+	pub subsumed_clauses: u64,
+	/// The number of clauses that were shortened by self-subsuming resolution
+	pub strengthened_clauses: u64,
+	/// The number of hidden or asymmetric tautologies that were removed during preprocessing
+	pub tautologies: u64,
+	/// The number of blocked clauses that were removed during preprocessing
+	pub blocked_clauses: u64,
+	/// The number of unit clauses that were learned because a probed literal failed
+	pub failed_literals: u64,
+	/// The number of unit clauses that were found because both values of a probed variable imply them
+	pub implied_units: u64,
+	/// The number of binary clauses that were added by hyper-binary resolution during probing
+	pub hyper_binary_resolvents: u64,
+	/// The number of clauses that were learned by conflict analysis
+	pub learned_clauses: u64,
+	/// The number of learned clauses that were deleted by reductions
+	pub deleted_clauses: u64,
+	/// The number of learned clauses that are kept forever
+	pub core_clauses: u64,
+	/// The number of learned clauses that are currently kept for as long as they are used
+	pub tier2_clauses: u64,
+	/// The number of learned clauses that are currently aged out by activity
+	pub local_clauses: u64,
+	/// The sum of the glues of all learned clauses
+	pub learned_glue: u64,
+	/// The deepest decision level that was reached
+	pub max_depth: usize,
+	/// The time spent on preprocessing before the search
+	pub preprocessing_time: Duration,
+	/// The time spent on the search, including conflict analysis, reductions and probing
+	pub search_time: Duration,
+	/// The time spent on conflict analysis
+	pub analysis_time: Duration,
+	/// The time spent on reductions of the learned clauses
+	pub reduction_time: Duration,
+	/// The time spent on probing during the search
+	pub probing_time: Duration,
+}
+
+impl Statistics {
+	pub fn average_glue(&self) -> f64 {
+		if self.learned_clauses == 0 {
+			0.0
+		} else {
+			self.learned_glue as f64 / self.learned_clauses as f64
+		}
+	}
+
+	pub fn print(&self, f: &mut impl io::Write, indent: &str) -> io::Result<()> {
+		writeln!(f, "{}{:20} {}", indent, "Decisions", self.decisions)?;
+		writeln!(f, "{}{:20} {}", indent, "Propagations", self.propagations)?;
+		writeln!(f, "{}{:20} {}", indent, "Conflicts", self.conflicts)?;
+		writeln!(f, "{}{:20} {}", indent, "Restarts", self.restarts)?;
+		writeln!(f, "{}{:20} {}", indent, "Reductions", self.reductions)?;
+		writeln!(
+			f,
+			"{}{:20} {}",
+			indent, "Eliminated variables", self.eliminated_variables
+		)?;
+		writeln!(
+			f,
+			"{}{:20} {}",
+			indent, "Equivalent variables", self.equivalent_variables
+		)?;
+		writeln!(f, "{}{:20} {}", indent, "Transitive binaries", self.transitive_binaries)?;
+		writeln!(f, "{}{:20} {}", indent, "Subsumed clauses", self.subsumed_clauses)?;
+		writeln!(
+			f,
+			"{}{:20} {}",
+			indent, "Strengthened clauses", self.strengthened_clauses
+		)?;
+		writeln!(f, "{}{:20} {}", indent, "Tautologies", self.tautologies)?;
+		writeln!(f, "{}{:20} {}", indent, "Blocked clauses", self.blocked_clauses)?;
+		writeln!(f, "{}{:20} {}", indent, "Failed literals", self.failed_literals)?;
+		writeln!(f, "{}{:20} {}", indent, "Implied units", self.implied_units)?;
+		writeln!(
+			f,
+			"{}{:20} {}",
+			indent, "Hyper-binary clauses", self.hyper_binary_resolvents
+		)?;
+		writeln!(f, "{}{:20} {}", indent, "Learned clauses", self.learned_clauses)?;
+		writeln!(f, "{}{:20} {}", indent, "Deleted clauses", self.deleted_clauses)?;
+		writeln!(f, "{}{:20} {}", indent, "Core clauses", self.core_clauses)?;
+		writeln!(f, "{}{:20} {}", indent, "Tier 2 clauses", self.tier2_clauses)?;
+		writeln!(f, "{}{:20} {}", indent, "Local clauses", self.local_clauses)?;
+		writeln!(f, "{}{:20} {:.2}", indent, "Average glue", self.average_glue())?;
+		writeln!(f, "{}{:20} {}", indent, "Maximum depth", self.max_depth)?;
+		for (name, duration) in [
+			("Preprocessing time", &self.preprocessing_time),
+			("Search time", &self.search_time),
+			("Analysis time", &self.analysis_time),
+			("Reduction time", &self.reduction_time),
+			("Probing time", &self.probing_time),
+		] {
+			writeln!(f, "{}{:20} {}", indent, name, DurationWrapper::new(duration))?;
+		}
+		Ok(())
+	}
+}
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use super::*;
 use crate::SolverResult;
 
@@ -100,3 +103,582 @@ fn unsat2() {
 	let result = problem.solve();
 	assert_eq!(result, SolverResult::Unsat);
 }
+
+#[test]
+fn assumptions_sat() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("x1", false).add_literal("x2", false);
+	let x1 = Literal::new(pb.variable_id("x1"), true);
+	let mut problem = pb.as_problem();
+	let result = problem.solve_with_assumptions(&[x1]);
+	assert_eq!(result, SolverResult::Sat);
+	assert_eq!(problem.model(), vec![(&"x1", false), (&"x2", true)]);
+}
+
+#[test]
+fn assumptions_unsat() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", true).add_literal("b", false);
+	pb.new_clause().add_literal("b", true).add_literal("c", false);
+	pb.new_clause().add_literal("d", false).add_literal("e", false);
+	let a = Literal::new(pb.variable_id("a"), false);
+	let not_c = Literal::new(pb.variable_id("c"), true);
+	let d = Literal::new(pb.variable_id("d"), false);
+	let mut problem = pb.as_problem();
+	let result = problem.solve_with_assumptions(&[a, d, not_c]);
+	assert_eq!(result, SolverResult::Unsat);
+	let mut failed = problem.failed_assumptions().to_vec();
+	failed.sort();
+	assert_eq!(failed, vec![a, not_c]);
+
+	let result = problem.solve_with_assumptions(&[d, not_c]);
+	assert_eq!(result, SolverResult::Sat);
+	assert!(problem.failed_assumptions().is_empty());
+
+	let result = problem.solve();
+	assert_eq!(result, SolverResult::Sat);
+}
+
+#[test]
+fn assumptions_contradictory() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("x1", false).add_literal("x2", false);
+	let x1 = pb.variable_id("x1");
+	let mut problem = pb.as_problem();
+	let result = problem.solve_with_assumptions(&[Literal::new(x1, false), Literal::new(x1, true)]);
+	assert_eq!(result, SolverResult::Unsat);
+	assert_eq!(problem.failed_assumptions().len(), 2);
+	assert_eq!(problem.solve(), SolverResult::Sat);
+}
+
+#[test]
+fn add_clauses() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", false).add_literal("b", false);
+	let a = pb.variable_id("a");
+	let b = pb.variable_id("b");
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.solve(), SolverResult::Sat);
+
+	problem.add_clause(ClauseLiteralVec::from_slice(&[Literal::new(a, true)]));
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	assert_eq!(problem.model(), vec![(&"a", false), (&"b", true)]);
+
+	problem.add_clause(ClauseLiteralVec::from_slice(&[Literal::new(b, true)]));
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+}
+
+#[test]
+fn add_variables() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", false).add_literal("b", false);
+	let a = pb.variable_id("a");
+	let b = pb.variable_id("b");
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.solve(), SolverResult::Sat);
+
+	let c = problem.add_variable("c");
+	problem.add_clause(ClauseLiteralVec::from_slice(&[
+		Literal::new(c, true),
+		Literal::new(a, false),
+	]));
+	problem.add_clause(ClauseLiteralVec::from_slice(&[
+		Literal::new(a, true),
+		Literal::new(b, true),
+	]));
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.add_clause(ClauseLiteralVec::from_slice(&[Literal::new(c, false)]));
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	assert_eq!(problem.model(), vec![(&"a", true), (&"b", false), (&"c", true)]);
+}
+
+#[test]
+fn verify_model() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", false).add_literal("b", false);
+	pb.new_clause().add_literal("a", true).add_literal("b", true);
+	pb.keep_original_clauses();
+	let a = pb.variable_id("a");
+	let mut problem = pb.as_problem();
+	assert!(matches!(problem.verify_model(), Err(errors::Error::NoModel)));
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+
+	problem.add_clause(ClauseLiteralVec::from_slice(&[Literal::new(a, false)]));
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+}
+
+#[test]
+fn verify_model_without_original() {
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", false);
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	assert!(matches!(problem.verify_model(), Err(errors::Error::NoOriginalClauses)));
+}
+
+fn pigeonhole(pigeons: usize) -> Problem<String> {
+	pigeonhole_builder(pigeons).as_problem()
+}
+
+fn pigeonhole_builder(pigeons: usize) -> ProblemBuilder<String> {
+	let holes = pigeons - 1;
+	let mut pb = ProblemBuilder::new();
+	for pigeon in 0..pigeons {
+		let mut clause = pb.new_clause();
+		for hole in 0..holes {
+			clause.add_literal(format!("p{}h{}", pigeon, hole), false);
+		}
+	}
+	for hole in 0..holes {
+		for a in 0..pigeons {
+			for b in a + 1..pigeons {
+				pb.new_clause()
+					.add_literal(format!("p{}h{}", a, hole), true)
+					.add_literal(format!("p{}h{}", b, hole), true);
+			}
+		}
+	}
+	pb
+}
+
+#[test]
+fn conflict_limit() {
+	let mut problem = pigeonhole(6);
+	problem.set_conflict_limit(Some(10));
+	let mut calls = 1;
+	while problem.solve() == SolverResult::Unknown {
+		calls += 1;
+	}
+	assert!(calls > 1);
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+}
+
+#[test]
+fn propagation_limit() {
+	let mut problem = pigeonhole(6);
+	problem.set_propagation_limit(Some(1));
+	assert_eq!(problem.solve(), SolverResult::Unknown);
+	problem.set_propagation_limit(None);
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+}
+
+#[test]
+fn terminate_flag() {
+	let flag = Arc::new(AtomicBool::new(true));
+	let mut problem = pigeonhole(6);
+	problem.set_terminate_flag(Some(Arc::clone(&flag)));
+	assert_eq!(problem.solve(), SolverResult::Unknown);
+	flag.store(false, Ordering::Relaxed);
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+}
+
+#[test]
+fn statistics() {
+	let mut problem = pigeonhole(7);
+	problem.set_conflict_limit(Some(10));
+	assert_eq!(problem.solve(), SolverResult::Unknown);
+	// the limit is only checked between conflicts
+	assert!(problem.stats().conflicts >= 10);
+	problem.set_conflict_limit(None);
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+	let stats = problem.stats();
+	assert!(stats.conflicts > 10);
+	assert!(stats.decisions >= stats.conflicts);
+	assert_eq!(stats.learned_clauses, stats.conflicts);
+	assert!(stats.average_glue() >= 1.0);
+	assert!(stats.max_depth > 0);
+	assert!(stats.propagations >= stats.decisions);
+}
+
+#[test]
+fn config() {
+	let mut pb = pigeonhole_builder(6);
+	pb.set_config(SolverConfig {
+		reduce_interval: 10,
+		reduce_increment: 0,
+		reduce_fraction: 1.0,
+		..SolverConfig::default()
+	});
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+	let stats = problem.stats();
+	assert!(stats.reductions > 1);
+	assert!(stats.deleted_clauses > 0);
+}
+
+#[test]
+fn restart_policies() {
+	for policy in [RestartPolicy::Luby, RestartPolicy::Geometric, RestartPolicy::Glucose] {
+		let mut pb = pigeonhole_builder(6);
+		pb.set_config(SolverConfig {
+			restart_policy: policy,
+			restart_interval: 4,
+			reduce_interval: 100,
+			..SolverConfig::default()
+		});
+		let mut problem = pb.as_problem();
+		assert_eq!(problem.solve(), SolverResult::Unsat);
+		let stats = problem.stats();
+		if policy != RestartPolicy::Glucose {
+			assert!(stats.restarts > 0);
+		}
+		assert!(stats.reductions > 0);
+	}
+}
+
+#[test]
+fn branching_heuristics() {
+	for policy in [
+		BranchingPolicy::Chb,
+		BranchingPolicy::Evsids,
+		BranchingPolicy::Vmtf,
+		BranchingPolicy::Lrb,
+	] {
+		let config = SolverConfig {
+			branching: policy,
+			..SolverConfig::default()
+		};
+		let mut pb = pigeonhole_builder(6);
+		pb.set_config(config.clone());
+		assert_eq!(pb.as_problem().solve(), SolverResult::Unsat);
+
+		// 5 pigeons fit into 5 holes
+		let mut pb = ProblemBuilder::new();
+		for pigeon in 0..5 {
+			let mut clause = pb.new_clause();
+			for hole in 0..5 {
+				clause.add_literal(5 * pigeon + hole, false);
+			}
+			for other in pigeon + 1..5 {
+				for hole in 0..5 {
+					pb.new_clause()
+						.add_literal(5 * pigeon + hole, true)
+						.add_literal(5 * other + hole, true);
+				}
+			}
+		}
+		pb.set_config(config);
+		let mut problem = pb.as_problem();
+		assert_eq!(problem.solve(), SolverResult::Sat);
+		let extra = problem.add_variable(25);
+		problem.add_clause([Literal::new(extra, false)].into_iter().collect());
+		assert_eq!(problem.solve(), SolverResult::Sat);
+	}
+}
+
+#[test]
+fn binary_implications() {
+	// x0 -> x1 -> ... -> x9 -> x0, where one of the variables has to be true
+	let mut pb = ProblemBuilder::new();
+	for i in 0..10 {
+		pb.new_clause().add_literal(i, true).add_literal((i + 1) % 10, false);
+	}
+	pb.new_clause()
+		.add_literal(3, false)
+		.add_literal(7, false)
+		.add_literal(8, false);
+	pb.keep_original_clauses();
+	let x5 = pb.variable_id(5);
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+	assert!(problem.model().into_iter().all(|(_, value)| value));
+
+	problem.add_clause(ClauseLiteralVec::from_slice(&[Literal::new(x5, true)]));
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+}
+
+#[test]
+fn clause_tiers() {
+	let solve = |core_glue, tier2_glue| {
+		let mut pb = pigeonhole_builder(6);
+		pb.set_config(SolverConfig {
+			reduce_interval: 20,
+			reduce_increment: 0,
+			core_glue,
+			tier2_glue,
+			..SolverConfig::default()
+		});
+		let mut problem = pb.as_problem();
+		assert_eq!(problem.solve(), SolverResult::Unsat);
+		problem.stats().clone()
+	};
+
+	let stats = solve(0, 0);
+	assert_eq!(stats.core_clauses, 0);
+	assert_eq!(stats.tier2_clauses, 0);
+	assert!(stats.deleted_clauses > 0);
+
+	let stats = solve(u32::MAX, u32::MAX);
+	assert!(stats.core_clauses > 0);
+	assert_eq!(stats.tier2_clauses + stats.local_clauses + stats.deleted_clauses, 0);
+
+	let stats = solve(2, 6);
+	assert!(
+		stats.core_clauses + stats.tier2_clauses + stats.local_clauses + stats.deleted_clauses <= stats.learned_clauses
+	);
+}
+
+#[test]
+fn preprocessing() {
+	// x0 and x0 -> x1 -> ... -> x20, given in reverse, next to a tautology and a clause that is only shortened
+	let mut pb = ProblemBuilder::new();
+	for i in (0..20).rev() {
+		pb.new_clause()
+			.add_literal(format!("x{}", i), true)
+			.add_literal(format!("x{}", i + 1), false)
+			.add_literal("y".to_string(), true);
+	}
+	pb.new_clause().add_literal("x0".to_string(), false);
+	pb.new_clause().add_literal("y".to_string(), false);
+	pb.new_clause()
+		.add_literal("a".to_string(), false)
+		.add_literal("b".to_string(), false)
+		.add_literal("a".to_string(), true);
+	pb.new_clause()
+		.add_literal("c".to_string(), false)
+		.add_literal("d".to_string(), false)
+		.add_literal("x20".to_string(), true);
+	let problem = pb.as_problem();
+	let mut clauses = Vec::new();
+	problem.print_clauses(&mut clauses).unwrap();
+	assert_eq!(String::from_utf8(clauses).unwrap(), " c  d \n");
+}
+
+#[test]
+fn subsumption() {
+	// a ∨ b subsumes a ∨ b ∨ c, and resolving it with ¬a ∨ b ∨ d ∨ e leaves b ∨ d ∨ e
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", false).add_literal("b", false);
+	pb.new_clause()
+		.add_literal("a", false)
+		.add_literal("b", false)
+		.add_literal("c", false);
+	pb.new_clause()
+		.add_literal("a", true)
+		.add_literal("b", false)
+		.add_literal("d", false)
+		.add_literal("e", false);
+	let problem = pb.as_problem();
+	assert_eq!(problem.stats().subsumed_clauses, 1);
+	assert_eq!(problem.stats().strengthened_clauses, 1);
+	let mut clauses = Vec::new();
+	problem.print_clauses(&mut clauses).unwrap();
+	assert_eq!(String::from_utf8(clauses).unwrap(), " b  d  e \n a  b \n");
+}
+
+#[test]
+fn probing() {
+	let mut pb = ProblemBuilder::new();
+	// p implies q and r, which imply both s and ¬s
+	pb.new_clause().add_literal("p", true).add_literal("q", false);
+	pb.new_clause().add_literal("p", true).add_literal("r", false);
+	for negated in [false, true] {
+		pb.new_clause()
+			.add_literal("q", true)
+			.add_literal("r", true)
+			.add_literal("s", negated);
+	}
+	// x implies a and ¬x implies b, both of which imply y
+	pb.new_clause().add_literal("x", true).add_literal("a", false);
+	pb.new_clause().add_literal("a", true).add_literal("y", false);
+	pb.new_clause().add_literal("x", false).add_literal("b", false);
+	pb.new_clause().add_literal("b", true).add_literal("y", false);
+	// u implies v and w, which imply z, so u implies z directly
+	pb.new_clause().add_literal("u", true).add_literal("v", false);
+	pb.new_clause().add_literal("u", true).add_literal("w", false);
+	pb.new_clause()
+		.add_literal("v", true)
+		.add_literal("w", true)
+		.add_literal("z", false);
+	pb.keep_original_clauses();
+	pb.set_config(SolverConfig {
+		probe: true,
+		..SolverConfig::default()
+	});
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.stats().failed_literals, 1);
+	assert_eq!(problem.stats().implied_units, 1);
+	assert_eq!(problem.stats().hyper_binary_resolvents, 1);
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+	let model = problem.model();
+	assert!(model.contains(&(&"p", false)) && model.contains(&(&"y", true)));
+}
+
+#[test]
+fn equivalent_literals() {
+	// x0 <-> ¬x1 <-> x2 <-> ... <-> ¬x9, where x0 or ¬x9 has to be true, next to x10 <-> x11
+	let builder = || {
+		let mut pb = ProblemBuilder::new();
+		for i in 0..9 {
+			pb.new_clause().add_literal(i, false).add_literal(i + 1, false);
+			pb.new_clause().add_literal(i, true).add_literal(i + 1, true);
+		}
+		pb.new_clause().add_literal(0, false).add_literal(9, true);
+		pb.new_clause().add_literal(10, true).add_literal(11, false);
+		pb.new_clause().add_literal(10, false).add_literal(11, true);
+		pb.keep_original_clauses();
+		pb.set_config(SolverConfig {
+			substitute: true,
+			..SolverConfig::default()
+		});
+		pb
+	};
+
+	let mut problem = builder().as_problem();
+	assert_eq!(problem.stats().equivalent_variables, 10);
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+	let model = problem.model();
+	assert!((0..10).all(|i| model[i].1 == (i % 2 == 0)));
+	assert_eq!(model[10].1, model[11].1);
+
+	// substituted variables are restored once they are used again
+	let mut pb = builder();
+	let (x5, x11) = (pb.variable_id(5), pb.variable_id(11));
+	let mut problem = pb.as_problem();
+	assert_eq!(
+		problem.solve_with_assumptions(&[Literal::new(x5, false)]),
+		SolverResult::Unsat
+	);
+	assert_eq!(
+		problem.solve_with_assumptions(&[Literal::new(x11, true)]),
+		SolverResult::Sat
+	);
+	problem.verify_model().unwrap();
+	assert!(!problem.model()[10].1);
+}
+
+#[test]
+fn transitive_reduction() {
+	// a -> b -> c makes a -> c redundant
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", true).add_literal("b", false);
+	pb.new_clause().add_literal("b", true).add_literal("c", false);
+	pb.new_clause().add_literal("a", true).add_literal("c", false);
+	pb.set_config(SolverConfig {
+		substitute: true,
+		..SolverConfig::default()
+	});
+	let problem = pb.as_problem();
+	assert_eq!(problem.stats().transitive_binaries, 1);
+}
+
+#[test]
+fn variable_elimination() {
+	// x0 <-> x1 <-> ... <-> x9, where x0 or x9 has to be true
+	let builder = |frozen: &[usize]| {
+		let mut pb = ProblemBuilder::new();
+		for i in 0..9 {
+			pb.new_clause().add_literal(i, true).add_literal(i + 1, false);
+			pb.new_clause().add_literal(i, false).add_literal(i + 1, true);
+		}
+		pb.new_clause().add_literal(0, false).add_literal(9, false);
+		for &i in frozen {
+			pb.freeze(i);
+		}
+		pb.keep_original_clauses();
+		pb.set_config(SolverConfig {
+			eliminate: true,
+			..SolverConfig::default()
+		});
+		pb
+	};
+
+	let mut problem = builder(&[]).as_problem();
+	assert!(problem.stats().eliminated_variables > 0);
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+	assert!(problem.model().into_iter().all(|(_, value)| value));
+
+	let frozen: Vec<usize> = (0..10).collect();
+	let problem = builder(&frozen).as_problem();
+	assert_eq!(problem.stats().eliminated_variables, 0);
+
+	// eliminated variables are restored once they are used again
+	let mut pb = builder(&[]);
+	let x5 = pb.variable_id(5);
+	let mut problem = pb.as_problem();
+	assert_eq!(
+		problem.solve_with_assumptions(&[Literal::new(x5, true)]),
+		SolverResult::Unsat
+	);
+	problem.add_clause(ClauseLiteralVec::from_slice(&[Literal::new(x5, true)]));
+	assert_eq!(problem.solve(), SolverResult::Unsat);
+}
+
+#[test]
+fn tautologies() {
+	// a ∨ c ∨ d is a hidden tautology due to ¬a -> b -> c, and e ∨ f ∨ g an asymmetric one due to e ∨ f ∨ x and ¬x ∨ g
+	let mut pb = ProblemBuilder::new();
+	pb.new_clause().add_literal("a", false).add_literal("b", false);
+	pb.new_clause().add_literal("b", true).add_literal("c", false);
+	pb.new_clause()
+		.add_literal("a", false)
+		.add_literal("c", false)
+		.add_literal("d", false);
+	pb.new_clause()
+		.add_literal("e", false)
+		.add_literal("f", false)
+		.add_literal("x", false);
+	pb.new_clause().add_literal("x", true).add_literal("g", false);
+	pb.new_clause()
+		.add_literal("e", false)
+		.add_literal("f", false)
+		.add_literal("g", false);
+	pb.keep_original_clauses();
+	pb.set_config(SolverConfig {
+		eliminate_tautologies: true,
+		..SolverConfig::default()
+	});
+	let mut problem = pb.as_problem();
+	assert_eq!(problem.stats().tautologies, 2);
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+}
+
+#[test]
+fn blocked_clauses() {
+	// g <-> a ∧ b, where g does not occur anywhere else, next to a xor b, which subsumes g ∨ ¬a ∨ ¬b
+	let builder = || {
+		let mut pb = ProblemBuilder::new();
+		pb.new_clause().add_literal("g", true).add_literal("a", false);
+		pb.new_clause().add_literal("g", true).add_literal("b", false);
+		pb.new_clause()
+			.add_literal("g", false)
+			.add_literal("a", true)
+			.add_literal("b", true);
+		pb.new_clause().add_literal("a", false).add_literal("b", false);
+		pb.new_clause().add_literal("a", true).add_literal("b", true);
+		pb.keep_original_clauses();
+		pb.set_config(SolverConfig {
+			eliminate_blocked: true,
+			..SolverConfig::default()
+		});
+		pb
+	};
+
+	let mut problem = builder().as_problem();
+	assert_eq!(problem.stats().blocked_clauses, 4);
+	assert_eq!(problem.solve(), SolverResult::Sat);
+	problem.verify_model().unwrap();
+	let model = problem.model();
+	assert!(!model[0].1 && model[1].1 != model[2].1);
+
+	// the clauses are restored once their witnesses are used again
+	let mut pb = builder();
+	let g = pb.variable_id("g");
+	let mut problem = pb.as_problem();
+	assert_eq!(
+		problem.solve_with_assumptions(&[Literal::new(g, false)]),
+		SolverResult::Unsat
+	);
+	assert_eq!(
+		problem.solve_with_assumptions(&[Literal::new(g, true)]),
+		SolverResult::Sat
+	);
+	problem.verify_model().unwrap();
+}
@@ -1,6 +1,6 @@
 use std::io;
 
-use super::{Clause, Literal, Problem, Variable, VariableId};
+use super::{ClauseRef, Literal, Problem, Variable, VariableId, Watcher};
 
 pub fn print_stats(f: &mut impl io::Write, indent: &str) -> io::Result<()> {
 	writeln!(
@@ -14,8 +14,15 @@ pub fn print_stats(f: &mut impl io::Write, indent: &str) -> io::Result<()> {
 		f,
 		"{}{:15} {:3}",
 		indent,
-		"Clause",
-		crate::util::Typeinfo::<Clause>::new()
+		"ClauseRef",
+		crate::util::Typeinfo::<ClauseRef>::new()
+	)?;
+	writeln!(
+		f,
+		"{}{:15} {:3}",
+		indent,
+		"Watcher",
+		crate::util::Typeinfo::<Watcher>::new()
 	)?;
 	writeln!(
 		f,
@@ -1,6 +1,7 @@
 // Proof that u32 is large enough:
 // 1 bit is lost due to literal compression, meaning that 2 billion variables are possible
-// Variables have a fixed cost of at least 72 byte, so just storing 2 billion variables will take around 150 GB.
+// Variables have a fixed cost of more than 100 byte (96 for the watch lists and implications, 15 for the assignment),
+// so just storing 2 billion variables will take more than 200 GB.
 // Additionally, any useful variable needs to be in at least 2 clauses, costing another 16 GB
 // Too bad, I am not convinced.
 #[cfg(feature = "small_variable_ids")]
@@ -72,103 +73,67 @@ mod variable_id_impl {
 
 pub use self::variable_id_impl::VariableId;
 
+use super::{ArenaRef, Literal};
+
+/// A binary clause as seen from one of its literals: once that literal is false, `literal` is implied
+#[derive(Debug, Clone, Copy)]
+pub struct Implication {
+	pub literal: Literal,
+	/// The id of the binary clause in the proof
+	pub id: u64,
+}
+
+/// An entry of a watch list. `blocker` is some other literal of the clause `cid`: while it is true, the clause is
+/// satisfied and does not need to be visited at all.
+#[derive(Debug, Clone, Copy)]
+pub struct Watcher {
+	pub cid: ArenaRef,
+	pub blocker: Literal,
+}
+
+/// The cold per-variable data: watch lists and binary implications. The current assignment lives in
+/// [`Assignment`](super::Assignment) instead.
 #[derive(Debug)]
 pub struct Variable {
-	q: f64,
-	watchlists: [Vec<usize>; 2],
-	ante: usize,
-	depth: VariableId,
-	value: bool,
+	watchlists: [Vec<Watcher>; 2],
+	implications: [Vec<Implication>; 2],
 }
 
 impl Variable {
 	pub fn new() -> Variable {
 		Variable {
 			watchlists: [Vec::new(), Vec::new()],
-			ante: usize::MAX,
-			depth: VariableId::MAX,
-			value: false,
-			q: 0.0,
-		}
-	}
-
-	pub fn has_value(&self) -> bool {
-		self.depth != VariableId::MAX
-	}
-
-	pub fn get_value(&self) -> bool {
-		debug_assert!(self.has_value());
-		self.value
-	}
-
-	pub fn set_phase(&mut self, value: bool) {
-		self.value = value;
-	}
-
-	pub fn value(&self) -> Option<bool> {
-		if self.depth == VariableId::MAX {
-			None
-		} else {
-			Some(self.value)
+			implications: [Vec::new(), Vec::new()],
 		}
 	}
 
-	pub fn set(&mut self, value: bool, depth: VariableId, ante: usize) {
-		self.ante = ante;
-		self.depth = depth;
-		self.value = value;
-	}
-
-	pub fn unset(&mut self) {
-		self.depth = VariableId::MAX;
-	}
-
-	pub fn enable(&mut self, depth: VariableId) {
-		self.ante = usize::MAX;
-		self.depth = depth;
-	}
-
-	pub fn get_depth(&self) -> VariableId {
-		self.depth
-	}
-
-	pub fn get_ante(&self) -> usize {
-		debug_assert!(self.has_value());
-		self.ante
+	pub fn get_watchers(&mut self, negative: bool) -> &mut Vec<Watcher> {
+		&mut self.watchlists[negative as usize]
 	}
 
-	pub fn get_clauses(&mut self, negative: bool) -> &mut Vec<usize> {
-		&mut self.watchlists[negative as usize]
+	pub fn watch(&mut self, watcher: Watcher, negated: bool) {
+		self.get_watchers(negated).push(watcher);
 	}
 
-	pub fn watch(&mut self, cid: usize, negated: bool) {
-		self.get_clauses(negated).push(cid);
+	/// The binary clauses that contain the literal of this variable with the given polarity
+	pub fn get_implications(&self, negative: bool) -> &[Implication] {
+		&self.implications[negative as usize]
 	}
 
-	pub fn unwatch(&mut self, cid: usize, negated: bool) {
-		let clauses = self.get_clauses(negated);
-		let pos = clauses.iter().rev().position(|&x| x == cid).unwrap();
-		clauses.swap_remove(clauses.len() - 1 - pos);
+	pub fn add_implication(&mut self, negated: bool, implication: Implication) {
+		self.implications[negated as usize].push(implication);
 	}
 
-	pub fn clear_watched(&mut self) {
-		self.watchlists[0].clear();
-		self.watchlists[1].clear();
+	/// Removes the binary clause `id` from the implications of the literal with the given polarity
+	pub fn remove_implication(&mut self, negated: bool, id: u64) {
+		self.implications[negated as usize].retain(|implication| implication.id != id);
 	}
 
-	pub fn watches(&self, cid: usize) -> bool {
+	pub fn watches(&self, cid: ArenaRef) -> bool {
 		self.watchlists[0]
 			.iter()
 			.chain(self.watchlists[1].iter())
-			.any(|&x| x == cid)
-	}
-
-	pub fn q(&self) -> &f64 {
-		&self.q
-	}
-
-	pub fn q_mut(&mut self) -> &mut f64 {
-		&mut self.q
+			.any(|watcher| watcher.cid == cid)
 	}
 }
 
@@ -0,0 +1,77 @@
+use crate::io::open_file;
+
+#[derive(clap::Parser, Debug)]
+#[clap(about = "Check a DRAT or LRAT proof of unsatisfiability", long_about = None)]
+pub struct Cli {
+	/// The path to the dimacs file
+	#[arg(value_name = "CNF")]
+	cnf: std::path::PathBuf,
+
+	/// The path to the proof, which may be textual or binary
+	#[arg(value_name = "PROOF")]
+	proof: std::path::PathBuf,
+
+	/// The proof is in LRAT instead of DRAT format
+	#[arg(long = "lrat")]
+	lrat: bool,
+
+	/// Time the checking process
+	#[arg(short = 't', long = "time")]
+	time: bool,
+}
+
+pub fn main(args: Cli) -> Result<(), super::errors::Error> {
+	let mut sw = crate::util::Stopwatch::new();
+
+	sw.start();
+	let mut reader = open_file(&args.cnf).map_err(|err| super::errors::Error::Read {
+		source: err,
+		path: args.cnf.display().to_string(),
+	})?;
+	let builder = crate::parser::dimacs::parse_builder(&mut reader).map_err(|err| super::errors::Error::Parse {
+		source: err,
+		path: args.cnf.display().to_string(),
+	})?;
+	sw.stop();
+	if args.time {
+		println!("[T] Parsing CNF: {}", sw);
+	}
+
+	sw.start();
+	let mut reader = open_file(&args.proof).map_err(|err| super::errors::Error::Read {
+		source: err,
+		path: args.proof.display().to_string(),
+	})?;
+	let parse_error = |err| super::errors::Error::Parse {
+		source: err,
+		path: args.proof.display().to_string(),
+	};
+	let result = if args.lrat {
+		let steps = crate::parser::proof::parse_lrat(&mut reader).map_err(parse_error)?;
+		sw.stop();
+		if args.time {
+			println!("[T] Parsing proof: {}", sw);
+		}
+		sw.start();
+		crate::checker::check_lrat(builder.clauses(), &steps)
+	} else {
+		let steps = crate::parser::proof::parse_drat(&mut reader).map_err(parse_error)?;
+		sw.stop();
+		if args.time {
+			println!("[T] Parsing proof: {}", sw);
+		}
+		sw.start();
+		crate::checker::check_drat(builder.clauses(), &steps)
+	};
+	sw.stop();
+	if args.time {
+		println!("[T] Checking proof: {}", sw);
+	}
+	let lemmas = result.map_err(|err| super::errors::Error::Check {
+		source: err,
+		path: args.proof.display().to_string(),
+	})?;
+	println!("Result: Verified ({} lemmas checked)", lemmas);
+
+	Ok(())
+}
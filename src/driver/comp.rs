@@ -1,14 +1,52 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::time::{Duration, Instant};
+
 use crate::SolverResult;
+use crate::cnf::{Proof, ProofFormat, SolverConfig};
 use crate::io::open_file;
 
-pub fn main(path: &str) -> Result<SolverResult, super::errors::Error> {
+pub fn main(
+	path: &str,
+	proof_path: Option<&str>,
+	timeout: Option<Duration>,
+	conflicts: Option<u64>,
+) -> Result<SolverResult, super::errors::Error> {
+	// signals and the timeout also cover parsing and preprocessing, which may take a while on large instances
+	let start = Instant::now();
+	let terminate = super::signals::terminate_flag()?;
 	let mut reader = open_file(std::path::Path::new(path)).map_err(|err| super::errors::Error::Read {
 		source: err,
 		path: path.into(),
 	})?;
-	let mut problem = crate::parser::dimacs::parse(&mut reader).map_err(|err| super::errors::Error::Parse {
+	let mut builder = crate::parser::dimacs::parse_builder(&mut reader).map_err(|err| super::errors::Error::Parse {
 		source: err,
 		path: path.into(),
 	})?;
-	Ok(problem.solve())
+	if let Some(proof_path) = proof_path {
+		let file = File::create(proof_path).map_err(|err| super::errors::Error::Write {
+			source: err,
+			path: proof_path.into(),
+		})?;
+		builder.set_proof(Proof::new(BufWriter::new(file), ProofFormat::Drat));
+	}
+	// competition instances are large enough for the preprocessing passes to pay off
+	builder.set_config(SolverConfig {
+		eliminate: true,
+		substitute: true,
+		probe: true,
+		..SolverConfig::default()
+	});
+	let mut problem = builder.as_problem();
+	problem.set_time_limit(timeout.map(|timeout| timeout.saturating_sub(start.elapsed())));
+	problem.set_conflict_limit(conflicts);
+	problem.set_terminate_flag(Some(terminate));
+	let result = problem.solve();
+	if let Some(proof_path) = proof_path {
+		problem.finish_proof().map_err(|err| super::errors::Error::Write {
+			source: err,
+			path: proof_path.into(),
+		})?;
+	}
+	Ok(result)
 }
@@ -1,4 +1,9 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::time::{Duration, Instant};
+
 use crate::SolverResult;
+use crate::cnf::{BranchingPolicy, Proof, ProofFormat, RestartPolicy, SolverConfig};
 use crate::io::open_file;
 
 #[derive(clap::Parser, Debug)]
@@ -19,9 +24,163 @@ pub struct Cli {
 	/// Dump a new dimacs file after preprocessing (note: this does not preserve names!)
 	#[arg(short = 'p', long = "preprocess")]
 	preprocess: bool,
+
+	/// Write a proof to FILE, which is always written but only derives the empty clause if the query is unsatisfiable
+	#[arg(long = "proof", value_name = "FILE")]
+	proof: Option<std::path::PathBuf>,
+
+	/// The format of the proof
+	#[arg(long = "proof-format", value_enum, default_value_t = ProofFormat::Drat)]
+	proof_format: ProofFormat,
+
+	/// Give up after SECONDS, counted from the start including parsing and preprocessing
+	#[arg(long = "timeout", value_name = "SECONDS")]
+	timeout: Option<u64>,
+
+	/// Give up after N conflicts
+	#[arg(long = "conflicts", value_name = "N")]
+	conflicts: Option<u64>,
+
+	/// Verify satisfying models against the original clauses
+	#[arg(long = "check")]
+	check: bool,
+
+	/// The branching heuristic that chooses the decision variables
+	#[arg(long = "branching", value_enum, default_value_t = SolverConfig::default().branching)]
+	branching: BranchingPolicy,
+
+	/// The initial step size of the CHB and LRB branching heuristics
+	#[arg(long = "alpha", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().alpha)]
+	alpha: f64,
+
+	/// The amount by which the step size of the CHB and LRB branching heuristics is reduced after each conflict
+	#[arg(long = "alpha-decay", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().alpha_decay)]
+	alpha_decay: f64,
+
+	/// The minimum step size of the CHB and LRB branching heuristics
+	#[arg(long = "alpha-min", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().alpha_min)]
+	alpha_min: f64,
+
+	/// The reward of propagations without conflict relative to conflicts in the CHB branching heuristic
+	#[arg(long = "propagation-reward", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().propagation_reward)]
+	propagation_reward: f64,
+
+	/// The decay of the activities of the EVSIDS branching heuristic after each conflict
+	#[arg(long = "vsids-decay", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().vsids_decay)]
+	vsids_decay: f64,
+
+	/// The number of conflicts before learned clauses are reduced for the first time
+	#[arg(long = "reduce-interval", value_name = "N", default_value_t = SolverConfig::default().reduce_interval)]
+	reduce_interval: u32,
+
+	/// The growth of the number of conflicts between reductions of learned clauses
+	#[arg(long = "reduce-increment", value_name = "N", default_value_t = SolverConfig::default().reduce_increment)]
+	reduce_increment: u32,
+
+	/// The fraction of the local tier of learned clauses that is deleted by each reduction
+	#[arg(long = "reduce-fraction", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().reduce_fraction)]
+	reduce_fraction: f64,
+
+	/// The highest glue of learned clauses that are kept forever
+	#[arg(long = "core-glue", value_name = "N", default_value_t = SolverConfig::default().core_glue)]
+	core_glue: u32,
+
+	/// The highest glue of learned clauses that are kept while they are used in conflict analysis
+	#[arg(long = "tier2-glue", value_name = "N", default_value_t = SolverConfig::default().tier2_glue)]
+	tier2_glue: u32,
+
+	/// The decay of the activities of learned clauses after each conflict
+	#[arg(long = "clause-decay", value_name = "X", value_parser = unit_interval, default_value_t = SolverConfig::default().clause_decay)]
+	clause_decay: f64,
+
+	/// When to restart the search
+	#[arg(long = "restarts", value_enum, default_value_t = SolverConfig::default().restart_policy)]
+	restart_policy: RestartPolicy,
+
+	/// The unit of Luby restarts, the initial interval of geometric restarts, or the minimum interval of glucose-style
+	/// restarts in conflicts
+	#[arg(long = "restart-interval", value_name = "N", value_parser = clap::value_parser!(u32).range(1..), default_value_t = SolverConfig::default().restart_interval)]
+	restart_interval: u32,
+
+	/// The growth of the interval of geometric restarts
+	#[arg(long = "restart-factor", value_name = "X", value_parser = at_least_one, default_value_t = SolverConfig::default().restart_factor)]
+	restart_factor: f64,
+
+	/// How much the recent average glue has to exceed the overall average glue for a glucose-style restart
+	#[arg(long = "restart-margin", value_name = "X", value_parser = at_least_one, default_value_t = SolverConfig::default().restart_margin)]
+	restart_margin: f64,
+
+	/// Whether to eliminate variables by resolution during preprocessing
+	#[arg(long = "eliminate", value_name = "BOOL", action = clap::ArgAction::Set, default_value_t = SolverConfig::default().eliminate)]
+	eliminate: bool,
+
+	/// Whether to substitute equivalent literals during preprocessing
+	#[arg(long = "substitute", value_name = "BOOL", action = clap::ArgAction::Set, default_value_t = SolverConfig::default().substitute)]
+	substitute: bool,
+
+	/// Whether to probe for failed literals during preprocessing and search
+	#[arg(long = "probe", value_name = "BOOL", action = clap::ArgAction::Set, default_value_t = SolverConfig::default().probe)]
+	probe: bool,
+
+	/// Whether to eliminate blocked clauses during preprocessing
+	#[arg(long = "eliminate-blocked", value_name = "BOOL", action = clap::ArgAction::Set, default_value_t = SolverConfig::default().eliminate_blocked)]
+	eliminate_blocked: bool,
+
+	/// Whether to eliminate hidden and asymmetric tautologies during preprocessing
+	#[arg(long = "eliminate-tautologies", value_name = "BOOL", action = clap::ArgAction::Set, default_value_t = SolverConfig::default().eliminate_tautologies)]
+	eliminate_tautologies: bool,
+}
+
+impl Cli {
+	fn config(&self) -> SolverConfig {
+		SolverConfig {
+			branching: self.branching,
+			alpha: self.alpha,
+			alpha_decay: self.alpha_decay,
+			alpha_min: self.alpha_min,
+			propagation_reward: self.propagation_reward,
+			vsids_decay: self.vsids_decay,
+			reduce_interval: self.reduce_interval,
+			reduce_increment: self.reduce_increment,
+			reduce_fraction: self.reduce_fraction,
+			core_glue: self.core_glue,
+			tier2_glue: self.tier2_glue,
+			clause_decay: self.clause_decay,
+			restart_policy: self.restart_policy,
+			restart_interval: self.restart_interval,
+			restart_factor: self.restart_factor,
+			restart_margin: self.restart_margin,
+			eliminate: self.eliminate,
+			substitute: self.substitute,
+			probe: self.probe,
+			eliminate_blocked: self.eliminate_blocked,
+			eliminate_tautologies: self.eliminate_tautologies,
+		}
+	}
+}
+
+fn unit_interval(arg: &str) -> Result<f64, String> {
+	let value: f64 = arg.parse().map_err(|err| format!("{}", err))?;
+	if (0.0..=1.0).contains(&value) {
+		Ok(value)
+	} else {
+		Err(format!("{} is not in the range [0, 1]", value))
+	}
+}
+
+fn at_least_one(arg: &str) -> Result<f64, String> {
+	let value: f64 = arg.parse().map_err(|err| format!("{}", err))?;
+	if value >= 1.0 {
+		Ok(value)
+	} else {
+		Err(format!("{} is less than 1", value))
+	}
 }
 
 pub fn main(args: Cli) -> Result<(), super::errors::Error> {
+	// signals and the timeout also cover parsing and preprocessing, which may take a while on large instances
+	let start = Instant::now();
+	let terminate = super::signals::terminate_flag()?;
 	let mut sw = crate::util::Stopwatch::new();
 
 	sw.start();
@@ -35,10 +194,29 @@ pub fn main(args: Cli) -> Result<(), super::errors::Error> {
 	}
 
 	sw.start();
-	let mut problem = crate::parser::dimacs::parse(&mut reader).map_err(|err| super::errors::Error::Parse {
+	let mut builder = crate::parser::dimacs::parse_builder(&mut reader).map_err(|err| super::errors::Error::Parse {
 		source: err,
 		path: args.path.display().to_string(),
 	})?;
+	if let Some(proof_path) = &args.proof {
+		let file = File::create(proof_path).map_err(|err| super::errors::Error::Write {
+			source: err,
+			path: proof_path.display().to_string(),
+		})?;
+		builder.set_proof(Proof::new(BufWriter::new(file), args.proof_format));
+	}
+	if args.check {
+		builder.keep_original_clauses();
+	}
+	builder.set_config(args.config());
+	let mut problem = builder.as_problem();
+	problem.set_time_limit(
+		args
+			.timeout
+			.map(|timeout| Duration::from_secs(timeout).saturating_sub(start.elapsed())),
+	);
+	problem.set_conflict_limit(args.conflicts);
+	problem.set_terminate_flag(Some(terminate));
 	sw.stop();
 	if args.time {
 		println!("[T] Parsing and preprocessing file: {}", sw);
@@ -50,9 +228,20 @@ pub fn main(args: Cli) -> Result<(), super::errors::Error> {
 	sw.start();
 	let result = problem.solve();
 	sw.stop();
+	if let Some(proof_path) = &args.proof {
+		problem.finish_proof().map_err(|err| super::errors::Error::Write {
+			source: err,
+			path: proof_path.display().to_string(),
+		})?;
+	}
 	if args.time {
 		println!("[T] Solving query: {}", sw);
-		problem.print_conflict_histo(&mut ::std::io::stdout())?;
+		problem.stats().print(&mut ::std::io::stdout(), "    ")?;
+	}
+	if args.check && result == SolverResult::Sat {
+		problem
+			.verify_model()
+			.map_err(|err| super::errors::Error::Model { source: err })?;
 	}
 	match result {
 		SolverResult::Sat => println!("Result: Satisfiable"),
@@ -19,6 +19,13 @@ pub enum Error {
 		path: String,
 	},
 
+	#[error("Error writing {path}")]
+	Write {
+		#[source]
+		source: std::io::Error,
+		path: String,
+	},
+
 	#[error("Error parsing {path}")]
 	Parse {
 		#[source]
@@ -26,6 +33,19 @@ pub enum Error {
 		path: String,
 	},
 
+	#[error("The proof {path} was rejected")]
+	Check {
+		#[source]
+		source: crate::checker::errors::Error,
+		path: String,
+	},
+
+	#[error("The model does not satisfy the query")]
+	Model {
+		#[source]
+		source: crate::cnf::errors::Error,
+	},
+
 	#[error("Invalid dimensions for Sudoko (maximum dimensions: 35x35)")]
 	InvalidSudokuDimensions,
 }
@@ -35,11 +55,16 @@ impl Error {
 	// 1 is reserved for clap
 	// 10 is reserved as the SAT competition result "SATISFIABLE"
 	// 20 is reserved as the SAT competition result "UNSATISFIABLE"
+	// 80 is used for proofs that fail to check, so that they can be told apart from unreadable ones
+	// 81 is used for models that fail to check, which indicates a bug in the solver
 	pub fn code(&self) -> i32 {
 		match *self {
 			Error::Read { .. } => 2,
 			Error::Parse { .. } => 2,
 			Error::ParseInt(..) => 2,
+			Error::Check { .. } => 80,
+			Error::Model { .. } => 81,
+			Error::Write { .. } => 100,
 			Error::Io(..) => 100,
 			Error::RawIo(..) => 100,
 			Error::InvalidSudokuDimensions => 126,
@@ -1,7 +1,9 @@
+pub mod check_proof;
 pub mod comp;
 pub mod completion;
 pub mod dimacs;
 pub mod errors;
 pub mod npn;
+pub mod signals;
 pub mod stats;
 pub mod sudoku;
@@ -15,6 +15,10 @@ pub struct Cli {
 	/// Dump the AST of the problem after parsing it
 	#[arg(long = "dump-ast")]
 	dump_ast: bool,
+
+	/// Verify satisfying models against the original clauses
+	#[arg(long = "check")]
+	check: bool,
 }
 
 pub fn main(args: Cli) -> Result<(), super::errors::Error> {
@@ -22,10 +26,14 @@ pub fn main(args: Cli) -> Result<(), super::errors::Error> {
 	let mut sw = crate::util::Stopwatch::new();
 
 	sw.start();
-	let mut problem = crate::parser::npn::parse(&mut reader).map_err(|err| super::errors::Error::Parse {
+	let mut builder = crate::parser::npn::parse_builder(&mut reader).map_err(|err| super::errors::Error::Parse {
 		source: err,
 		path: "-".into(),
 	})?;
+	if args.check {
+		builder.keep_original_clauses();
+	}
+	let mut problem = builder.as_problem();
 	sw.stop();
 	if args.time {
 		println!("[T] Parsing query: {}", sw);
@@ -40,7 +48,12 @@ pub fn main(args: Cli) -> Result<(), super::errors::Error> {
 	sw.stop();
 	if args.time {
 		println!("[T] Solving query: {}", sw);
-		problem.print_conflict_histo(&mut ::std::io::stdout())?;
+		problem.stats().print(&mut ::std::io::stdout(), "    ")?;
+	}
+	if args.check && result == SolverResult::Sat {
+		problem
+			.verify_model()
+			.map_err(|err| super::errors::Error::Model { source: err })?;
 	}
 	match result {
 		SolverResult::Sat => {
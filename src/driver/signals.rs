@@ -0,0 +1,16 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use signal_hook::consts::{SIGINT, SIGTERM};
+
+/// Returns a flag that is set by SIGINT and SIGTERM, so that the solver can stop gracefully. A second signal terminates
+/// the process immediately.
+pub fn terminate_flag() -> Result<Arc<AtomicBool>, super::errors::Error> {
+	let flag = Arc::new(AtomicBool::new(false));
+	for signal in [SIGINT, SIGTERM] {
+		// the order matters: the shutdown only triggers if the flag was already set by an earlier signal
+		signal_hook::flag::register_conditional_shutdown(signal, 1, Arc::clone(&flag))?;
+		signal_hook::flag::register(signal, Arc::clone(&flag))?;
+	}
+	Ok(flag)
+}
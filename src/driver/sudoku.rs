@@ -25,6 +25,10 @@ pub struct Cli {
 	#[arg(short = 'q', long = "query", value_name = "FILE")]
 	query: Option<std::path::PathBuf>,
 
+	/// Verify the solution against the original clauses of the SAT query
+	#[arg(long = "check")]
+	check: bool,
+
 	#[arg(short = 'r', long = "rows", default_value_t = 3)]
 	rows: usize,
 
@@ -75,11 +79,13 @@ pub fn main(args: Cli) -> Result<(), super::errors::Error> {
 	}
 
 	sw.start();
-	let result = board.solve();
+	let (result, stats) = board
+		.solve_with_stats(args.check)
+		.map_err(|err| super::errors::Error::Model { source: err })?;
 	sw.stop();
 	if args.time {
 		println!("[T] Solving query: {}", sw);
-		// FIXME: decide what to do here // problem.print_conflict_histo();
+		stats.print(&mut ::std::io::stdout(), "    ")?;
 	}
 
 	// FIXME: print result
@@ -1,3 +1,4 @@
+pub mod checker;
 pub mod cnf;
 pub mod driver;
 pub mod gp;
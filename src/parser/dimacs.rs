@@ -159,6 +159,13 @@ fn parse_clause(reader: &mut impl BufRead, builder: &mut ProblemBuilder<usize>)
 }
 
 pub fn parse(reader: &mut impl BufRead) -> Result<Problem<usize>, super::errors::Error> {
+	Ok(parse_builder(reader)?.as_problem())
+}
+
+/// Parses a dimacs file without creating the problem yet, so that it can be configured further.
+///
+/// The variables are created in the order of their names, i.e., the variable `n` is assigned the id `n - 1`.
+pub fn parse_builder(reader: &mut impl BufRead) -> Result<ProblemBuilder<usize>, super::errors::Error> {
 	skip_comments(reader)?;
 	let mut builder = ProblemBuilder::new();
 	let (variables, clauses) = parse_header(reader)?;
@@ -167,6 +174,9 @@ pub fn parse(reader: &mut impl BufRead) -> Result<Problem<usize>, super::errors:
 	}
 	builder.reserve_variables(variables);
 	builder.reserve_clauses(clauses);
+	for name in 1..=variables {
+		builder.variable_id(name);
+	}
 	for _ in 0..clauses {
 		parse_clause(reader, &mut builder)?;
 	}
@@ -179,5 +189,5 @@ pub fn parse(reader: &mut impl BufRead) -> Result<Problem<usize>, super::errors:
 	// anything else in the file, we explicitly ignore
 	// considering the many different ways dimacs files end, this
 	// is explicitly done to increase compatibility
-	Ok(builder.as_problem())
+	Ok(builder)
 }
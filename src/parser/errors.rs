@@ -30,4 +30,12 @@ pub enum Error {
 
 	#[error("Expected {expected} variables, but encountered {actual}")]
 	VariableCount { expected: usize, actual: usize },
+
+	// proof specific
+	#[error("Error on line {line}")]
+	Line {
+		#[source]
+		source: Box<Error>,
+		line: usize,
+	},
 }
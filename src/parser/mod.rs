@@ -1,4 +1,5 @@
 pub mod dimacs;
 pub mod errors;
 pub mod npn;
+pub mod proof;
 pub mod sudoku;
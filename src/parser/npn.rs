@@ -159,6 +159,11 @@ fn parse_clause(reader: &mut impl BufRead, builder: &mut ProblemBuilder<usize>)
 }
 
 pub fn parse(reader: &mut impl BufRead) -> Result<Problem<usize>, super::errors::Error> {
+	Ok(parse_builder(reader)?.as_problem())
+}
+
+/// Parses a query without creating the problem yet, so that it can be configured further
+pub fn parse_builder(reader: &mut impl BufRead) -> Result<ProblemBuilder<usize>, super::errors::Error> {
 	skip_comments(reader)?;
 	let mut builder = ProblemBuilder::new();
 	let (variables, clauses) = parse_header(reader)?;
@@ -179,5 +184,5 @@ pub fn parse(reader: &mut impl BufRead) -> Result<Problem<usize>, super::errors:
 	// anything else in the file, we explicitly ignore
 	// considering the many different ways dimacs files end, this
 	// is explicitly done to increase compatibility
-	Ok(builder.as_problem())
+	Ok(builder)
 }
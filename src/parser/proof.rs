@@ -0,0 +1,271 @@
+use std::io::BufRead;
+
+use crate::checker::{DratStep, LratStep};
+use crate::cnf::{Literal, VariableId};
+
+// larger variables can not be represented with 32-bit literals and do not occur in practice
+const MAX_VARIABLE: u64 = i32::MAX as u64;
+
+// The whole proof is kept in memory by the checker anyway, so it is simpler to parse it from a single buffer.
+struct Input {
+	data: Vec<u8>,
+	pos: usize,
+	binary: bool,
+	line: usize,
+}
+
+impl Input {
+	fn new(reader: &mut impl BufRead) -> Result<Input, super::errors::Error> {
+		let mut data = Vec::new();
+		reader.read_to_end(&mut data)?;
+		let binary = is_binary(&data);
+		Ok(Input {
+			data,
+			pos: 0,
+			binary,
+			line: if binary { 0 } else { 1 },
+		})
+	}
+
+	fn peek(&self) -> Option<u8> {
+		self.data.get(self.pos).copied()
+	}
+
+	// skips whitespace and comments, returning false at the end of the input
+	fn skip_ws(&mut self) -> bool {
+		while let Some(byte) = self.peek() {
+			match byte {
+				b'\n' => self.line += 1,
+				b'c' => {
+					while self.peek().is_some_and(|byte| byte != b'\n') {
+						self.pos += 1;
+					}
+					continue;
+				}
+				_ if is_ws(byte) => {}
+				_ => return true,
+			}
+			self.pos += 1;
+		}
+		false
+	}
+
+	// skips the 'd' that marks a deletion in text proofs
+	fn skip_deletion_marker(&mut self) -> bool {
+		if self.skip_ws() && self.peek() == Some(b'd') {
+			self.pos += 1;
+			true
+		} else {
+			false
+		}
+	}
+
+	fn text_int(&mut self) -> Result<i64, super::errors::Error> {
+		if !self.skip_ws() {
+			return Err(super::errors::Error::ExpectedIntOrNeg);
+		}
+		let negated = self.peek() == Some(b'-');
+		if negated {
+			self.pos += 1;
+		}
+		let start = self.pos;
+		let mut result: u64 = 0;
+		while let Some(dig) = self.peek().map(|byte| byte.wrapping_sub(b'0')).filter(|&dig| dig <= 9) {
+			result = result
+				.checked_mul(10)
+				.and_then(|result| result.checked_add(dig as u64))
+				.filter(|&result| result <= i64::MAX as u64)
+				.ok_or(super::errors::Error::Overflow)?;
+			self.pos += 1;
+		}
+		if self.pos == start {
+			return Err(match self.peek() {
+				Some(byte) => super::errors::Error::UnexpectedByte(byte),
+				None => super::errors::Error::ExpectedInt,
+			});
+		}
+		Ok(if negated { -(result as i64) } else { result as i64 })
+	}
+
+	// reads a number of a binary proof, which is mapped from x to 2|x| + (x < 0)
+	fn binary_int(&mut self) -> Result<i64, super::errors::Error> {
+		let mut result: u64 = 0;
+		let mut shift = 0;
+		loop {
+			let byte = self.peek().ok_or(super::errors::Error::ExpectedInt)?;
+			self.pos += 1;
+			if shift > 56 {
+				return Err(super::errors::Error::Overflow);
+			}
+			result |= ((byte & 0x7f) as u64) << shift;
+			if byte & 0x80 == 0 {
+				break;
+			}
+			shift += 7;
+		}
+		let magnitude = (result >> 1) as i64;
+		Ok(if result & 1 != 0 { -magnitude } else { magnitude })
+	}
+
+	fn int(&mut self) -> Result<i64, super::errors::Error> {
+		if self.binary {
+			self.binary_int()
+		} else {
+			self.text_int()
+		}
+	}
+
+	// reads the kind of the next binary step, returning None at the end of the input
+	fn binary_marker(&mut self) -> Result<Option<u8>, super::errors::Error> {
+		let Some(byte) = self.peek() else {
+			return Ok(None);
+		};
+		self.line += 1;
+		match byte {
+			b'a' | b'd' => {
+				self.pos += 1;
+				Ok(Some(byte))
+			}
+			_ => Err(super::errors::Error::UnexpectedByte(byte)),
+		}
+	}
+
+	fn literals(&mut self) -> Result<Vec<Literal>, super::errors::Error> {
+		let mut literals = Vec::new();
+		loop {
+			let value = self.int()?;
+			if value == 0 {
+				return Ok(literals);
+			}
+			if value.unsigned_abs() > MAX_VARIABLE {
+				return Err(super::errors::Error::Overflow);
+			}
+			literals.push(Literal::new(
+				VariableId::from_usize(value.unsigned_abs() as usize - 1),
+				value < 0,
+			));
+		}
+	}
+
+	fn ids(&mut self) -> Result<Vec<i64>, super::errors::Error> {
+		let mut ids = Vec::new();
+		loop {
+			match self.int()? {
+				0 => return Ok(ids),
+				id => ids.push(id),
+			}
+		}
+	}
+
+	fn clause_id(&mut self) -> Result<u64, super::errors::Error> {
+		match self.int()? {
+			id if id > 0 => Ok(id as u64),
+			_ => Err(super::errors::Error::ExpectedInt),
+		}
+	}
+
+	fn error(&self, source: super::errors::Error) -> super::errors::Error {
+		super::errors::Error::Line {
+			line: self.line,
+			source: Box::new(source),
+		}
+	}
+}
+
+fn is_ws(byte: u8) -> bool {
+	let x = byte.wrapping_sub(9);
+	byte == b' ' || x < 5
+}
+
+// Text proofs only consist of digits, whitespace, signs, deletion markers and comments, while binary proofs start with
+// an 'a' or 'd' that is directly followed by arbitrary bytes. Similar to drat-trim, the first few bytes after any
+// leading comments decide.
+fn is_binary(mut data: &[u8]) -> bool {
+	loop {
+		let start = data.iter().position(|&byte| !is_ws(byte)).unwrap_or(data.len());
+		data = &data[start..];
+		if data.first() != Some(&b'c') {
+			break;
+		}
+		data = &data[data.iter().position(|&byte| byte == b'\n').unwrap_or(data.len())..];
+	}
+	data
+		.iter()
+		.take(16)
+		.any(|&byte| !(is_ws(byte) || byte.is_ascii_digit() || matches!(byte, b'-' | b'd')))
+}
+
+/// Parses a DRAT proof, which may be either textual or binary.
+///
+/// Each step is returned together with its line number; for binary proofs, the index of the step is used instead.
+pub fn parse_drat(reader: &mut impl BufRead) -> Result<Vec<(usize, DratStep)>, super::errors::Error> {
+	let mut input = Input::new(reader)?;
+	let mut steps = Vec::new();
+	loop {
+		let deletion = if input.binary {
+			match input.binary_marker().map_err(|err| input.error(err))? {
+				None => break,
+				Some(marker) => marker == b'd',
+			}
+		} else if !input.skip_ws() {
+			break;
+		} else {
+			input.skip_deletion_marker()
+		};
+		let line = input.line;
+		let literals = input.literals().map_err(|err| input.error(err))?;
+		steps.push((
+			line,
+			if deletion {
+				DratStep::Delete(literals)
+			} else {
+				DratStep::Add(literals)
+			},
+		));
+	}
+	Ok(steps)
+}
+
+/// Parses an LRAT proof, which may be either textual or binary.
+///
+/// Each step is returned together with its line number; for binary proofs, the index of the step is used instead.
+pub fn parse_lrat(reader: &mut impl BufRead) -> Result<Vec<(usize, LratStep)>, super::errors::Error> {
+	let mut input = Input::new(reader)?;
+	let mut steps = Vec::new();
+	loop {
+		let deletion = if input.binary {
+			match input.binary_marker().map_err(|err| input.error(err))? {
+				None => break,
+				Some(marker) => marker == b'd',
+			}
+		} else if !input.skip_ws() {
+			break;
+		} else {
+			false
+		};
+		let line = input.line;
+		let step = (|| {
+			let id = input.clause_id()?;
+			if deletion || (!input.binary && input.skip_deletion_marker()) {
+				// the id in front of a textual deletion only repeats the last added id
+				let mut ids = input.ids()?;
+				if input.binary {
+					ids.insert(0, id as i64);
+				}
+				Ok(LratStep::Delete(
+					ids
+						.into_iter()
+						.map(|id| u64::try_from(id).map_err(|_| super::errors::Error::ExpectedInt))
+						.collect::<Result<_, _>>()?,
+				))
+			} else {
+				let literals = input.literals()?;
+				let hints = input.ids()?;
+				Ok(LratStep::Add { id, literals, hints })
+			}
+		})()
+		.map_err(|err| input.error(err))?;
+		steps.push((line, step));
+	}
+	Ok(steps)
+}
@@ -1,7 +1,7 @@
 use std::io;
 
 use crate::SolverResult;
-use crate::cnf::{Problem, ProblemBuilder};
+use crate::cnf::{Problem, ProblemBuilder, Statistics};
 
 pub struct Board {
 	count: usize,
@@ -139,7 +139,7 @@ impl Board {
 		}
 	}
 
-	fn create_problem(&self) -> Option<Problem<usize>> {
+	fn create_problem(&self, keep_original: bool) -> Option<Problem<usize>> {
 		let mut pb = ProblemBuilder::new();
 
 		// each cell must contain one of the possbilities
@@ -223,17 +223,43 @@ impl Board {
 			}
 		}
 
+		// the solution is read off the cell variables
+		for offset in 0..self.data.len() {
+			if self.data[offset] {
+				pb.freeze(offset);
+			}
+		}
+
+		if keep_original {
+			pb.keep_original_clauses();
+		}
 		Some(pb.as_problem())
 	}
 
 	pub fn solve(&self) -> Option<Vec<usize>> {
-		if let Some(mut problem) = self.create_problem() {
-			match problem.solve() {
-				SolverResult::Unsat => None,
+		self.solve_impl(false).expect("models are only verified on request").0
+	}
+
+	/// Solves the puzzle like `solve`, but also returns the statistics of the SAT solver, which are empty if the puzzle
+	/// was found to be impossible without solving the query. With `check`, the model of the SAT query is verified
+	/// against its original clauses.
+	pub fn solve_with_stats(&self, check: bool) -> Result<(Option<Vec<usize>>, Statistics), crate::cnf::errors::Error> {
+		self.solve_impl(check)
+	}
+
+	fn solve_impl(&self, check: bool) -> Result<(Option<Vec<usize>>, Statistics), crate::cnf::errors::Error> {
+		if let Some(mut problem) = self.create_problem(check) {
+			let result = problem.solve();
+			let stats = problem.stats().clone();
+			match result {
+				SolverResult::Unsat => Ok((None, stats)),
 				SolverResult::Unknown => {
 					panic!("solver returned an unknown result");
 				}
 				SolverResult::Sat => {
+					if check {
+						problem.verify_model()?;
+					}
 					let model = problem.model();
 					let mut solution = vec![0; self.count * self.count];
 					for t in model.iter().filter(|t| t.1) {
@@ -241,16 +267,16 @@ impl Board {
 						debug_assert_eq!(solution[*t.0 / self.count], 0);
 						solution[*t.0 / self.count] = *t.0 % self.count + 1;
 					}
-					Some(solution)
+					Ok((Some(solution), stats))
 				}
 			}
 		} else {
-			None
+			Ok((None, Statistics::default()))
 		}
 	}
 
 	pub fn print_dimacs(&self, writer: &mut impl io::Write) -> io::Result<()> {
-		let problem = self.create_problem(); // FIXME: the model is generated twice...
+		let problem = self.create_problem(false); // FIXME: the model is generated twice...
 		if let Some(problem) = problem {
 			problem.print_dimacs(writer)
 		} else {
@@ -0,0 +1,146 @@
+// marks indices that are not contained in the heap
+const ABSENT: usize = usize::MAX;
+
+/// A binary max-heap of indices, which are ordered by scores that are stored elsewhere. Ties are broken in favor of
+/// the larger index.
+///
+/// Every operation receives the current scores, so that the score of an index may change while it is contained in the
+/// heap, as long as `update` is called afterwards.
+#[derive(Debug, Clone)]
+pub struct Heap {
+	heap: Vec<usize>,
+	positions: Vec<usize>,
+}
+
+impl Heap {
+	pub fn new() -> Heap {
+		Heap {
+			heap: Vec::new(),
+			positions: Vec::new(),
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.heap.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.heap.is_empty()
+	}
+
+	pub fn contains(&self, index: usize) -> bool {
+		self.positions.get(index).is_some_and(|&pos| pos != ABSENT)
+	}
+
+	/// Inserts `index`, unless it is already contained
+	pub fn push(&mut self, index: usize, score: impl Fn(usize) -> f64) {
+		if self.positions.len() <= index {
+			self.positions.resize(index + 1, ABSENT);
+		} else if self.positions[index] != ABSENT {
+			return;
+		}
+		self.positions[index] = self.heap.len();
+		self.heap.push(index);
+		self.sift_up(self.heap.len() - 1, &score);
+	}
+
+	/// Restores the heap order after the score of `index` has changed, if it is contained
+	pub fn update(&mut self, index: usize, score: impl Fn(usize) -> f64) {
+		if let Some(&pos) = self.positions.get(index).filter(|&&pos| pos != ABSENT) {
+			let pos = self.sift_up(pos, &score);
+			self.sift_down(pos, &score);
+		}
+	}
+
+	/// Removes and returns the index with the highest score
+	pub fn pop(&mut self, score: impl Fn(usize) -> f64) -> Option<usize> {
+		let top = *self.heap.first()?;
+		let last = self.heap.pop().unwrap();
+		self.positions[top] = ABSENT;
+		if !self.heap.is_empty() {
+			self.heap[0] = last;
+			self.positions[last] = 0;
+			self.sift_down(0, &score);
+		}
+		Some(top)
+	}
+
+	// returns the new position of the element at `pos`
+	fn sift_up(&mut self, mut pos: usize, score: &impl Fn(usize) -> f64) -> usize {
+		let index = self.heap[pos];
+		while pos > 0 {
+			let parent = (pos - 1) / 2;
+			if !precedes(index, self.heap[parent], score) {
+				break;
+			}
+			self.heap[pos] = self.heap[parent];
+			self.positions[self.heap[pos]] = pos;
+			pos = parent;
+		}
+		self.heap[pos] = index;
+		self.positions[index] = pos;
+		pos
+	}
+
+	fn sift_down(&mut self, mut pos: usize, score: &impl Fn(usize) -> f64) {
+		let index = self.heap[pos];
+		loop {
+			let left = 2 * pos + 1;
+			if left >= self.heap.len() {
+				break;
+			}
+			let right = left + 1;
+			let child = if right < self.heap.len() && precedes(self.heap[right], self.heap[left], score) {
+				right
+			} else {
+				left
+			};
+			if !precedes(self.heap[child], index, score) {
+				break;
+			}
+			self.heap[pos] = self.heap[child];
+			self.positions[self.heap[pos]] = pos;
+			pos = child;
+		}
+		self.heap[pos] = index;
+		self.positions[index] = pos;
+	}
+}
+
+fn precedes(a: usize, b: usize, score: &impl Fn(usize) -> f64) -> bool {
+	let (sa, sb) = (score(a), score(b));
+	sa > sb || (sa == sb && a > b)
+}
+
+impl Default for Heap {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn order() {
+		let mut scores = [3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0, 6.0, 3.0];
+		let mut heap = Heap::new();
+		for index in 0..scores.len() {
+			heap.push(index, |i| scores[i]);
+		}
+		heap.push(5, |i| scores[i]);
+		assert_eq!(heap.len(), scores.len());
+		assert_eq!(heap.pop(|i| scores[i]), Some(5));
+		assert!(!heap.contains(5));
+		scores[1] = 10.0;
+		heap.update(1, |i| scores[i]);
+		scores[7] = 0.0;
+		heap.update(7, |i| scores[i]);
+		let mut order = Vec::new();
+		while let Some(index) = heap.pop(|i| scores[i]) {
+			order.push(index);
+		}
+		assert_eq!(order, [1, 4, 2, 8, 0, 6, 3, 7]);
+	}
+}
@@ -0,0 +1,131 @@
+/// A set of indices that remembers which indices were inserted, so that clearing it only takes time proportional to
+/// the number of insertions instead of the largest index
+#[derive(Debug, Clone, Default)]
+pub struct Marks {
+	marked: Vec<bool>,
+	touched: Vec<usize>,
+}
+
+impl Marks {
+	pub fn new() -> Marks {
+		Default::default()
+	}
+
+	#[inline]
+	pub fn contains(&self, index: usize) -> bool {
+		self.marked.get(index).copied().unwrap_or(false)
+	}
+
+	/// Inserts `index`, returning whether it was not contained before
+	#[inline]
+	pub fn insert(&mut self, index: usize) -> bool {
+		if self.marked.len() <= index {
+			self.marked.resize(index + 1, false);
+		}
+		if self.marked[index] {
+			false
+		} else {
+			self.marked[index] = true;
+			self.touched.push(index);
+			true
+		}
+	}
+
+	#[inline]
+	pub fn remove(&mut self, index: usize) {
+		if let Some(marked) = self.marked.get_mut(index) {
+			*marked = false;
+		}
+	}
+
+	pub fn clear(&mut self) {
+		for index in self.touched.drain(..) {
+			self.marked[index] = false;
+		}
+	}
+}
+
+/// A set of indices that is cleared in constant time by starting a new generation
+#[derive(Debug, Clone)]
+pub struct Stamps {
+	stamps: Vec<u32>,
+	generation: u32,
+}
+
+impl Stamps {
+	pub fn new() -> Stamps {
+		Stamps {
+			stamps: Vec::new(),
+			generation: 1,
+		}
+	}
+
+	#[inline]
+	pub fn contains(&self, index: usize) -> bool {
+		self.stamps.get(index) == Some(&self.generation)
+	}
+
+	/// Inserts `index`, returning whether it was not contained before
+	#[inline]
+	pub fn insert(&mut self, index: usize) -> bool {
+		if self.stamps.len() <= index {
+			self.stamps.resize(index + 1, 0);
+		}
+		if self.stamps[index] == self.generation {
+			false
+		} else {
+			self.stamps[index] = self.generation;
+			true
+		}
+	}
+
+	pub fn clear(&mut self) {
+		self.generation = self.generation.wrapping_add(1);
+		if self.generation == 0 {
+			// stamps of the very first generation could otherwise be mistaken for current ones
+			self.stamps.fill(0);
+			self.generation = 1;
+		}
+	}
+}
+
+impl Default for Stamps {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn marks() {
+		let mut marks = Marks::new();
+		assert!(marks.insert(7));
+		assert!(!marks.insert(7));
+		assert!(marks.insert(2));
+		marks.remove(2);
+		assert!(!marks.contains(2));
+		assert!(marks.contains(7));
+		assert!(!marks.contains(100));
+		marks.clear();
+		assert!(!marks.contains(7));
+		assert!(marks.insert(7));
+	}
+
+	#[test]
+	fn stamps() {
+		let mut stamps = Stamps::new();
+		assert!(stamps.insert(3));
+		assert!(!stamps.insert(3));
+		stamps.clear();
+		assert!(!stamps.contains(3));
+		assert!(stamps.insert(3));
+		stamps.generation = u32::MAX;
+		assert!(stamps.insert(5));
+		stamps.clear();
+		assert!(!stamps.contains(5));
+		assert!(!stamps.contains(3));
+	}
+}
@@ -1,8 +1,19 @@
+mod heap;
+pub use self::heap::Heap;
+
 mod histo;
 pub use self::histo::Histo;
 
+mod marks;
+pub use self::marks::{Marks, Stamps};
+
+#[cfg(test)]
+mod shared_buffer;
+#[cfg(test)]
+pub use self::shared_buffer::SharedBuffer;
+
 mod stopwatch;
-pub use self::stopwatch::Stopwatch;
+pub use self::stopwatch::{DurationWrapper, Stopwatch};
 
 mod typeinfo;
 pub use self::typeinfo::Typeinfo;
@@ -0,0 +1,25 @@
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+/// A writer whose clones all append to the same buffer, so that tests can read what was written through a writer that
+/// was handed away
+#[derive(Debug, Clone, Default)]
+pub struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+	/// Returns everything that was written so far and empties the buffer
+	pub fn take(&self) -> Vec<u8> {
+		self.0.take()
+	}
+}
+
+impl io::Write for SharedBuffer {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.0.borrow_mut().write(buf)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
@@ -23,6 +23,10 @@ impl Stopwatch {
 	pub fn stop(&mut self) {
 		self.stop = Instant::now();
 	}
+
+	pub fn elapsed(&self) -> Duration {
+		self.stop - self.start
+	}
 }
 
 impl Default for Stopwatch {
@@ -88,10 +92,16 @@ fn format_duration(f: &mut fmt::Formatter, duration: &Duration) -> fmt::Result {
 	}
 }
 
-struct DurationWrapper<'a> {
+pub struct DurationWrapper<'a> {
 	duration: &'a Duration,
 }
 
+impl<'a> DurationWrapper<'a> {
+	pub fn new(duration: &'a Duration) -> DurationWrapper<'a> {
+		DurationWrapper { duration }
+	}
+}
+
 impl<'a> fmt::Display for DurationWrapper<'a> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		format_duration(f, self.duration)